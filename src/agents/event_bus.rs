@@ -1,47 +1,139 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::HashMap;
 use yew::worker::*;
 
+/// Events of the game published to the bus.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum GameEvent {
+    // GameStarted: The name of each player and "True" if he is controlled manually.
+    GameStarted { players: Vec<(String, bool)> },
+    ShotFired { idx_player: usize, idx_target: usize, idx_cell: usize, is_hit: bool },
+    // ShipSunk: "num_shots" is the number of shots from the first hit on the ship to its sinking.
+    ShipSunk { idx_player: usize, idx_target: usize, size: usize, num_shots: usize },
+    // HitStreak: The player has hit "length" times in a row.
+    HitStreak { idx_player: usize, length: usize },
+    GameWon { winners: Vec<usize> },
+}
+
+/// Kinds of the events to subscribe to.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    GameStarted,
+    ShotFired,
+    ShipSunk,
+    HitStreak,
+    GameWon,
+}
+
+impl GameEvent {
+    pub fn get_kind(&self) -> EventKind {
+        match self {
+            GameEvent::GameStarted { .. } => EventKind::GameStarted,
+            GameEvent::ShotFired { .. } => EventKind::ShotFired,
+            GameEvent::ShipSunk { .. } => EventKind::ShipSunk,
+            GameEvent::HitStreak { .. } => EventKind::HitStreak,
+            GameEvent::GameWon { .. } => EventKind::GameWon,
+        }
+    }
+
+    /// Returns "True" if the player with the index takes part in the event.
+    /// The start of the game concerns all players.
+    pub fn is_about_player(&self, idx: usize) -> bool {
+        match self {
+            GameEvent::GameStarted { .. } => true,
+            GameEvent::ShotFired { idx_player, idx_target, .. }
+            | GameEvent::ShipSunk { idx_player, idx_target, .. } => *idx_player == idx || *idx_target == idx,
+            GameEvent::HitStreak { idx_player, .. } => *idx_player == idx,
+            GameEvent::GameWon { winners } => winners.contains(&idx),
+        }
+    }
+}
+
+/// The events a subscriber wants to receive.
+/// Every field set to "None" matches any event.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Default)]
+pub struct Topic {
+    // board: The name of the board that published the event.
+    pub board: Option<String>,
+    // player: The index of the player who takes part in the event.
+    pub player: Option<usize>,
+    pub kinds: Option<Vec<EventKind>>,
+}
+
+impl Topic {
+    pub fn matches(&self, envelope: &Envelope) -> bool {
+        self.board.as_ref().map_or(true, |board| *board == envelope.board)
+            && self.player.map_or(true, |idx| envelope.event.is_about_player(idx))
+            && self.kinds.as_ref().map_or(true, |kinds| kinds.contains(&envelope.event.get_kind()))
+    }
+}
+
+/// The event delivered to the subscribers.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct Envelope {
+    // sequence: The number of the event on the bus, the events are delivered in this order.
+    pub sequence: u64,
+    pub board: String,
+    pub event: GameEvent,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum Request {
-    EventBusMsg(String),
+    // Publish: The board with the name publishes the event.
+    Publish(String, GameEvent),
+    // Subscribe: Replaces the topic of the subscriber, which receives all events by default.
+    Subscribe(Topic),
 }
 
 pub struct EventBus {
     link: AgentLink<EventBus>,
-    subscribers: HashSet<HandlerId>,
+    subscribers: HashMap<HandlerId, Topic>,
+    // sequence: The number of the next event.
+    sequence: u64,
 }
 
 impl Agent for EventBus {
     type Reach = Context<Self>;
     type Message = ();
     type Input = Request;
-    type Output = String;
+    type Output = Envelope;
 
     fn create(link: AgentLink<Self>) -> Self {
         Self {
             link,
-            subscribers: HashSet::new(),
+            subscribers: HashMap::new(),
+            sequence: 0,
         }
     }
 
     fn update(&mut self, _msg: Self::Message) {}
 
     fn connected(&mut self, id: HandlerId) {
-        self.subscribers.insert(id);
+        self.subscribers.insert(id, Topic::default());
     }
 
-    fn handle_input(&mut self, msg: Self::Input, _id: HandlerId) {
+    fn handle_input(&mut self, msg: Self::Input, id: HandlerId) {
         match msg {
-            Request::EventBusMsg(s) => {
-                for sub in self.subscribers.iter() {
-                    self.link.respond(*sub, s.clone());
+            Request::Publish(board, event) => {
+                let envelope = Envelope {
+                    sequence: self.sequence,
+                    board,
+                    event,
+                };
+                self.sequence += 1;
+                for (sub, topic) in self.subscribers.iter() {
+                    if sub.is_respondable() && topic.matches(&envelope) {
+                        self.link.respond(*sub, envelope.clone());
+                    }
                 }
             }
+            Request::Subscribe(topic) => {
+                self.subscribers.insert(id, topic);
+            }
         }
     }
 
     fn disconnected(&mut self, id: HandlerId) {
         self.subscribers.remove(&id);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,109 @@
+use yew::{ComponentLink, Component, Html, html, classes, Bridge, Bridged};
+use crate::agents::event_bus::{EventBus, Envelope, GameEvent, Request, Topic};
+use crate::components::play_board;
+use crate::objects::achievements::{self, Achievement, AchievementTracker, UnlockedAchievement};
+use crate::objects::daily::get_today;
+use crate::i18n::{tr, trf};
+
+pub enum Msg {
+    EventReceived(Envelope),
+}
+
+/// Unlocks achievements by the events of the game and shows them.
+pub struct AchievementsPanel {
+    tracker: AchievementTracker,
+    unlocked: Vec<UnlockedAchievement>,
+    // new_achievements: The achievements unlocked during the current game.
+    new_achievements: Vec<(Achievement, String)>,
+    _event_bus: Box<dyn Bridge<EventBus>>,
+}
+
+impl Component for AchievementsPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut event_bus = EventBus::bridge(link.callback(Msg::EventReceived));
+        event_bus.send(Request::Subscribe(Topic {
+            board: Some(play_board::BOARD_NAME.to_string()),
+            ..Topic::default()
+        }));
+        Self {
+            tracker: AchievementTracker::default(),
+            unlocked: achievements::load_unlocked(),
+            new_achievements: Vec::new(),
+            _event_bus: event_bus,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> bool {
+        match msg {
+            Msg::EventReceived(Envelope { event, .. }) => {
+                if let GameEvent::GameStarted { .. } = event {
+                    self.new_achievements.clear();
+                }
+                let mut is_changed: bool = false;
+                for (achievement, profile) in self.tracker.handle_event(&event) {
+                    if achievements::unlock(achievement, profile.as_str(), get_today().as_str()) {
+                        self.new_achievements.push((achievement, profile));
+                        is_changed = true;
+                    }
+                }
+                if is_changed {
+                    self.unlocked = achievements::load_unlocked();
+                }
+                is_changed
+            }
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> bool {
+        false
+    }
+
+    fn view(&self) -> Html {
+        let notice: Html = match self.new_achievements.last() {
+            None => { html! {} }
+            Some((achievement, profile)) => {
+                html! {
+                    <p class="achievement-new">
+                        { trf("achievement.unlocked", &[profile.as_str(), achievement.get_name().as_str()]) }
+                    </p>
+                }
+            }
+        };
+        html! {
+            <div class="achievements">
+                <h2>{ tr("achievement.title") }</h2>
+                { notice }
+                <ul>
+                    { for Achievement::all().iter().map(|achievement| self.view_achievement(*achievement)) }
+                </ul>
+            </div>
+        }
+    }
+}
+
+impl AchievementsPanel {
+    /// Renders the badge with the profiles that unlocked it.
+    fn view_achievement(&self, achievement: Achievement) -> Html {
+        let profiles: Vec<String> = self.unlocked.iter()
+            .filter(|unlocked| unlocked.achievement == achievement)
+            .map(|unlocked| unlocked.profile.clone())
+            .collect();
+        let class_unlocked: Option<&str> = if profiles.is_empty() { None } else { Some("unlocked") };
+        html! {
+            <li class=classes!("achievement", class_unlocked)>
+                <strong>{ achievement.get_name() }</strong>
+                { format!(": {}", achievement.get_description()) }
+                {
+                    if profiles.is_empty() {
+                        html! {}
+                    } else {
+                        html! { <span class="achievement-profiles">{ format!(" ({})", profiles.join(", ")) }</span> }
+                    }
+                }
+            </li>
+        }
+    }
+}
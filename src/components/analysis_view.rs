@@ -0,0 +1,95 @@
+use yew::{Component, ComponentLink, Html, html, Properties};
+use crate::components::coach_panel::CoachSummary;
+use crate::i18n::{tr, trf, tr_count};
+use crate::objects::analysis::PlayerStats;
+use crate::objects::coach::ShotRating;
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct AnalysisViewProps {
+    // winners: The names of the winners, None if the game ended without them.
+    pub winners: Option<String>,
+    // names_players, stats: The names and the statistics of the shots of all players in order.
+    pub names_players: Vec<String>,
+    pub stats: Vec<PlayerStats>,
+    // humans: The indexes of the players controlled by people, for whom the coach summary is shown.
+    pub humans: Vec<usize>,
+    pub ratings: Vec<ShotRating>,
+    pub width_field: usize,
+}
+
+/// The winners, the statistics of the shots of each player
+/// and the summary of the shot coach for the human players after the game.
+pub struct AnalysisView {
+    props: AnalysisViewProps,
+}
+
+impl Component for AnalysisView {
+    type Message = ();
+    type Properties = AnalysisViewProps;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> bool {
+        if self.props == props {
+            return false;
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let title: String = match &self.props.winners {
+            None => { tr("board.game_over") }
+            Some(names_winners) => { trf("board.won", &[names_winners.as_str()]) }
+        };
+        html! {
+            <div class="analysis">
+                <h1 class="title">{ title }</h1>
+                <table class="scores">
+                    <caption>{ tr("analysis.title") }</caption>
+                    <tr>
+                        <th>{ tr("analysis.player") }</th>
+                        <th>{ tr("analysis.shots") }</th>
+                        <th>{ tr("analysis.accuracy") }</th>
+                        <th>{ tr("analysis.first_hit") }</th>
+                        <th>{ tr("analysis.to_sink") }</th>
+                        <th>{ tr("analysis.miss_streak") }</th>
+                    </tr>
+                    { for self.props.names_players.iter().zip(self.props.stats.iter())
+                        .map(|(name_player, stats)| self.view_player_stats(name_player, stats)) }
+                </table>
+                { for self.props.humans.iter().map(|&idx_player| html! {
+                    <CoachSummary idx_player=idx_player ratings=self.props.ratings.clone()
+                        names_players=self.props.names_players.clone() width_field=self.props.width_field />
+                }) }
+            </div>
+        }
+    }
+}
+
+impl AnalysisView {
+    /// Renders the row of the statistics of the player.
+    fn view_player_stats(&self, name_player: &str, stats: &PlayerStats) -> Html {
+        let text_first_hit: String = stats.shots_to_first_hit.map_or("-".to_string(), |num| num.to_string());
+        let text_to_sink: String = stats.shots_to_sink.iter()
+            .map(|&(size, average)| format!("{}: {:.1}", size, average))
+            .collect::<Vec<String>>()
+            .join(", ");
+        html! {
+            <tr>
+                <td>{ name_player }</td>
+                <td>{ format!("{} ({})", stats.num_shots, tr_count("analysis.hits", stats.num_hits)) }</td>
+                <td>{ format!("{:.0}%", stats.get_accuracy() * 100.0) }</td>
+                <td>{ text_first_hit }</td>
+                <td>{ if text_to_sink.is_empty() { "-".to_string() } else { text_to_sink } }</td>
+                <td>{ stats.longest_miss_streak }</td>
+            </tr>
+        }
+    }
+}
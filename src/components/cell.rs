@@ -1,11 +1,34 @@
-use yew::{classes, html, Component, ComponentLink, Html, Callback, Bridged, Bridge};
+use yew::{classes, html, Component, ComponentLink, Html, Callback, Bridged, Bridge, MouseEvent, TouchEvent};
 use yew::html::Properties;
+use yew::services::TimeoutService;
+use yew::services::timeout::TimeoutTask;
+use std::time::Duration;
 use crate::agents::agent_w;
 use crate::agents::agent_w::Response;
+use crate::objects::game::Annotation;
+use crate::i18n::tr;
+
+// LONG_PRESS_MILLIS: How long the cell is held on a touch screen to mark it instead of a shot.
+const LONG_PRESS_MILLIS: u64 = 500;
+
+/// Returns the number of fingers on the screen, the pinch of the browser uses two of them.
+fn get_touch_count(e: &TouchEvent) -> u32 {
+    js_sys::Reflect::get(e, &"touches".into())
+        .and_then(|touches| js_sys::Reflect::get(&touches, &"length".into()))
+        .ok()
+        .and_then(|length| length.as_f64())
+        .unwrap_or(1.0) as u32
+}
 
 pub enum Msg {
     ClickCell(usize),
     UpdateCell(agent_w::Response),
+    // Marking the cell by the right click or the context menu of the long press.
+    AnnotateCell,
+    // One finger touched the cell ("True"), or it left the cell or the second finger started a pinch.
+    TouchChanged(bool),
+    // Marking the cell by the long press.
+    LongPressed,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -18,14 +41,92 @@ pub enum State {
     Deck,
     // Fire: The cell had already been shot, and the deck of the ship was knocked out.
     Fire,
+    // Sunk: The deck of the ship on the cell was knocked out, and the whole ship is sunk.
+    Sunk,
+    // Water: The cell was not shot, but there can be no deck on it, for example near a sunk ship.
+    Water,
+}
+
+/// The sides of the cell along which the outline of a sunk ship goes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Outline {
+    pub top: bool,
+    pub right: bool,
+    pub bottom: bool,
+    pub left: bool,
 }
 
-#[derive(Clone, Copy)]
+/// The position of the cell in the playing field in which the first cell has
+/// coordinates (0,0) and is located in the upper left angle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Coordinates {
     pub x: usize,
     pub y: usize,
 }
 
+impl Coordinates {
+    pub fn from_index(idx_cell: usize, width_field: usize) -> Self {
+        Self {
+            x: idx_cell % width_field,
+            y: idx_cell / width_field,
+        }
+    }
+
+    pub fn to_index(self, width_field: usize) -> usize {
+        self.y * width_field + self.x
+    }
+
+    /// Returns the name of the cell in A1 notation, for example "C5".
+    pub fn to_a1(self) -> String {
+        format!("{}{}", get_column_name(self.x), self.y + 1)
+    }
+
+    /// Parses the name of the cell in A1 notation, letters in any case.
+    pub fn from_a1(text: &str) -> Option<Self> {
+        let text: &str = text.trim();
+        let idx_digits: usize = text.find(|c: char| c.is_ascii_digit())?;
+        let (letters, digits) = text.split_at(idx_digits);
+        if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        // The columns are numbered like in spreadsheets: A..Z, AA, AB..
+        let mut column: usize = 0;
+        for c in letters.chars() {
+            column = column.checked_mul(26)?.checked_add((c.to_ascii_uppercase() as u8 - b'A') as usize + 1)?;
+        }
+        let row: usize = digits.parse::<usize>().ok()?;
+        if row == 0 {
+            return None;
+        }
+        Some(Self {
+            x: column - 1,
+            y: row - 1,
+        })
+    }
+}
+
+/// Returns the name of the cell in A1 notation, for example "C5".
+pub fn get_cell_name(idx_cell: usize, width_field: usize) -> String {
+    Coordinates::from_index(idx_cell, width_field).to_a1()
+}
+
+/// Returns the id of the element of the cell on the page.
+pub fn get_cell_element_id(idx_field: usize, idx_cell: usize) -> String {
+    format!("cell-{}-{}", idx_field, idx_cell)
+}
+
+/// Returns the letters of the column with the index, for example "A" for 0 and "AA" for 26.
+pub fn get_column_name(idx_column: usize) -> String {
+    let mut column: usize = idx_column + 1;
+    let mut letters: Vec<char> = Vec::new();
+    while column > 0 {
+        column -= 1;
+        letters.push((b'A' + (column % 26) as u8) as char);
+        column /= 26;
+    }
+    letters.iter().rev().collect()
+}
+
 
 #[derive(Clone, Debug, PartialEq, Properties)]
 pub struct Props {
@@ -34,6 +135,31 @@ pub struct Props {
     pub is_fog_of_war: bool,
     pub is_interactive: bool,
     pub msg_click_cell: Callback<usize>,
+    // intensity: The strength of the highlighting of an unknown cell from 0 to 1,
+    //            for example the probability that it contains a deck.
+    #[prop_or_default]
+    pub intensity: Option<f64>,
+    // label: The text shown in the corner of the cell.
+    #[prop_or_default]
+    pub label: Option<String>,
+    // is_highlighted: "True" if the cell is pointed at from outside the field, for example in the history.
+    #[prop_or_default]
+    pub is_highlighted: bool,
+    // is_cursor: "True" if the keyboard cursor is on the cell.
+    #[prop_or_default]
+    pub is_cursor: bool,
+    // name: The coordinate of the cell in A1 notation announced by screen readers.
+    #[prop_or_default]
+    pub name: String,
+    // outline: The sides of the cell on which the outline of a sunk ship is drawn.
+    #[prop_or_default]
+    pub outline: Outline,
+    // annotation: The mark put on the cell by the player.
+    #[prop_or_default]
+    pub annotation: Option<Annotation>,
+    // msg_annotate_cell: Called to change the mark of the cell, None if it cannot be marked.
+    #[prop_or_default]
+    pub msg_annotate_cell: Option<Callback<usize>>,
     // pub is_player_field: bool,
     // pub parent_call: Callback<bool>,
 }
@@ -46,10 +172,10 @@ pub struct PlayCell {
     link: ComponentLink<Self>,
     pub props: Props,
     _producer: Box<dyn Bridge<agent_w::Worker>>,
-
-    // coordinates: The position of the cell in the playing field in which the first cell has
-    //              coordinates (0,0) and is located in the upper left angle.
-    // pub coordinates: Coordinates,
+    // long_press: The timer of the touch that marks the cell when it expires.
+    long_press: Option<TimeoutTask>,
+    // is_long_pressed: "True" if the touch marked the cell, so the click after it is not a shot.
+    is_long_pressed: bool,
 }
 
 impl Component for PlayCell {
@@ -66,12 +192,18 @@ impl Component for PlayCell {
             state: State::Blank,
             props,
             _producer,
+            long_press: None,
+            is_long_pressed: false,
         }
     }
 
     fn update(&mut self, msg: Self::Message) -> bool {
         match msg {
             Msg::ClickCell(idx_cell) => {
+                if self.is_long_pressed {
+                    self.is_long_pressed = false;
+                    return false;
+                }
                 // Used to switch the cell in manual mode
                 if self.props.is_interactive {
                     if !self.is_fire() || !self.is_miss() {
@@ -83,6 +215,39 @@ impl Component for PlayCell {
                 false
             }
 
+            Msg::AnnotateCell => {
+                // The long press on a touch screen also opens the context menu,
+                // the cell is marked only once by whichever comes first.
+                if self.is_long_pressed {
+                    return false;
+                }
+                if self.long_press.take().is_some() {
+                    self.is_long_pressed = true;
+                }
+                self.emit_annotation();
+                false
+            }
+            Msg::LongPressed => {
+                self.long_press = None;
+                self.is_long_pressed = true;
+                self.emit_annotation();
+                false
+            }
+            Msg::TouchChanged(is_touched) => {
+                // The click that follows the long press comes after the finger leaves the cell.
+                if is_touched {
+                    self.is_long_pressed = false;
+                }
+                self.long_press = if is_touched && self.props.msg_annotate_cell.is_some() {
+                    Some(TimeoutService::spawn(
+                        Duration::from_millis(LONG_PRESS_MILLIS),
+                        self.link.callback(|_| Msg::LongPressed),
+                    ))
+                } else {
+                    None
+                };
+                false
+            }
             Msg::UpdateCell(response) => {
                 match response {
                     Response::ResultStateCell(idx_cell, idx_field, text) => {
@@ -96,6 +261,10 @@ impl Component for PlayCell {
                                 self.set_deck();
                             } else if text == "Blank" {
                                 self.set_blank();
+                            } else if text == "Sunk" {
+                                self.set_sunk();
+                            } else if text == "Water" {
+                                self.set_water();
                             }
                             return true;
                         }
@@ -126,25 +295,77 @@ impl Component for PlayCell {
             State::Blank => { "cell-blank" }
             State::Miss => { "cell-miss" }
             State::Fire => { "cell-fire" }
+            State::Sunk => { "cell-sunk" }
+            State::Water => { "cell-water" }
             State::Deck => {
                 if self.props.is_fog_of_war {
                     "cell-blank"
                 } else { "cell-deck" }
             }
         };
+        // Only the cells about which nothing is known are highlighted.
+        let style: String = match self.props.intensity {
+            Some(intensity) if cell_status == "cell-blank" => {
+                format!("background-color: rgba(255, 88, 88, {:.2})", intensity.clamp(0.0, 1.0))
+            }
+            _ => { String::new() }
+        };
+        let label: Html = match &self.props.label {
+            None => { html! {} }
+            Some(text) => { html! { <span class="cell-label">{ text }</span> } }
+        };
+        let class_highlighted: Option<&str> = if self.props.is_highlighted { Some("cell-highlighted") } else { None };
+        // The screen readers announce the coordinate and what is known about the cell.
+        let text_state: String = match self.get_state() {
+            State::Blank => { if self.props.is_fog_of_war { tr("cell.unknown") } else { tr("cell.water") } }
+            State::Deck => { if self.props.is_fog_of_war { tr("cell.unknown") } else { tr("cell.ship") } }
+            State::Miss => { tr("shot.miss") }
+            State::Fire => { tr("shot.hit") }
+            State::Sunk => { tr("cell.sunk") }
+            State::Water => { tr("cell.water") }
+        };
+        // The marks are shown only on the cells about which nothing is known.
+        let annotation: Option<Annotation> = self.props.annotation.filter(|_| cell_status == "cell-blank");
+        let (class_annotation, text_annotation) = match annotation {
+            None => { (None, String::new()) }
+            Some(Annotation::Water) => { (Some("annotation-water"), format!(", {}", tr("cell.marked_water"))) }
+            Some(Annotation::Ship) => { (Some("annotation-ship"), format!(", {}", tr("cell.marked_ship"))) }
+        };
+        let aria_label: String = format!("{}, {}{}", self.props.name, text_state, text_annotation);
+        let id: String = get_cell_element_id(self.props.idx_field, self.props.idx_cell);
+        let class_cursor: Option<&str> = if self.props.is_cursor { Some("cell-cursor") } else { None };
+        let outline: Outline = self.props.outline;
+        let class_outline: Vec<&str> = vec![
+            (outline.top, "outline-top"),
+            (outline.right, "outline-right"),
+            (outline.bottom, "outline-bottom"),
+            (outline.left, "outline-left"),
+        ].into_iter().filter(|(is_drawn, _)| *is_drawn).map(|(_, class)| class).collect();
         let idx_cell: usize = self.props.idx_cell;
         let result_html: Html = if self.props.is_interactive {
             let r: Html = html! {
-                <div key=self.props.idx_cell class=classes!("game-cells")
-                    onclick=self.link.callback(move |_| Msg::ClickCell(idx_cell))>
-                    <div class=classes!(cell_status)></div>
+                <div key=self.props.idx_cell class=classes!("game-cells", class_highlighted, class_cursor, class_annotation)
+                    style=style.clone() id=id.clone() role="gridcell" aria-label=aria_label.clone()
+                    onclick=self.link.callback(move |_| Msg::ClickCell(idx_cell))
+                    oncontextmenu=self.get_context_menu_callback()
+                    ontouchstart=self.link.callback(|e: TouchEvent| Msg::TouchChanged(get_touch_count(&e) == 1))
+                    ontouchend=self.link.callback(|_| Msg::TouchChanged(false))
+                    ontouchmove=self.link.callback(|_| Msg::TouchChanged(false))>
+                    <div class=classes!(cell_status, class_outline.clone())></div>
+                    { label }
                 </div>
             };
             r
         } else {
             let r: Html = html! {
-                <div key=self.props.idx_cell class=classes!("game-cells")>
-                    <div class=classes!(cell_status)></div>
+                <div key=self.props.idx_cell class=classes!("game-cells", class_highlighted, class_cursor, class_annotation)
+                    style=style id=id role="gridcell" aria-label=aria_label
+                    oncontextmenu=self.get_context_menu_callback()
+                    ontouchstart=self.link.callback(|e: TouchEvent| Msg::TouchChanged(get_touch_count(&e) == 1))
+                    ontouchend=self.link.callback(|_| Msg::TouchChanged(false))
+                    ontouchmove=self.link.callback(|_| Msg::TouchChanged(false))>
+                    <div class=classes!(cell_status, class_outline.clone())></div>
+                    { label }
                 </div>
             };
             r
@@ -154,6 +375,26 @@ impl Component for PlayCell {
 }
 
 impl PlayCell {
+    /// Asks the field to change the mark of the cell.
+    fn emit_annotation(&self) {
+        if let Some(msg_annotate_cell) = &self.props.msg_annotate_cell {
+            msg_annotate_cell.emit(self.props.idx_cell);
+        }
+    }
+
+    /// Returns the handler of the right click that marks the cell instead of showing the menu.
+    fn get_context_menu_callback(&self) -> Callback<MouseEvent> {
+        let is_annotated: bool = self.props.msg_annotate_cell.is_some();
+        self.link.batch_callback(move |e: MouseEvent| {
+            if is_annotated {
+                e.prevent_default();
+                vec![Msg::AnnotateCell]
+            } else {
+                vec![]
+            }
+        })
+    }
+
     pub fn get_state(&self) -> State {
         self.state
     }
@@ -174,6 +415,14 @@ impl PlayCell {
         self.state = State::Blank;
     }
 
+    pub fn set_sunk(&mut self) {
+        self.state = State::Sunk;
+    }
+
+    pub fn set_water(&mut self) {
+        self.state = State::Water;
+    }
+
     pub fn is_fire(&self) -> bool {
         self.state == State::Fire
     }
@@ -192,7 +441,7 @@ impl PlayCell {
 
     /// Toggle cell state
     pub fn toggle(&mut self) {
-        if self.is_blank() {
+        if self.is_blank() || self.state == State::Water {
             self.set_miss();
         } else if self.is_deck() {
             self.set_fire();
@@ -201,3 +450,52 @@ impl PlayCell {
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_columns_like_spreadsheets() {
+        assert_eq!(get_column_name(0), "A");
+        assert_eq!(get_column_name(25), "Z");
+        assert_eq!(get_column_name(26), "AA");
+        assert_eq!(get_column_name(27), "AB");
+        assert_eq!(get_column_name(51), "AZ");
+        assert_eq!(get_column_name(52), "BA");
+        assert_eq!(get_column_name(701), "ZZ");
+        assert_eq!(get_column_name(702), "AAA");
+    }
+
+    #[test]
+    fn parses_cells_in_any_case() {
+        assert_eq!(Coordinates::from_a1("C5"), Some(Coordinates { x: 2, y: 4 }));
+        assert_eq!(Coordinates::from_a1("c5"), Some(Coordinates { x: 2, y: 4 }));
+        assert_eq!(Coordinates::from_a1(" j10 "), Some(Coordinates { x: 9, y: 9 }));
+        assert_eq!(Coordinates::from_a1("aA3"), Some(Coordinates { x: 26, y: 2 }));
+    }
+
+    #[test]
+    fn rejects_malformed_cells() {
+        for text in ["", "A", "5", "A0", "1A", "A-1", "A1B", "Ж5", "A 5", "A99999999999999999999999"].iter() {
+            assert_eq!(Coordinates::from_a1(text), None, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn leaves_range_check_to_field() {
+        // Cells outside the field are parsed, the callers compare them with the size of the field.
+        assert_eq!(Coordinates::from_a1("Z30"), Some(Coordinates { x: 25, y: 29 }));
+    }
+
+    #[test]
+    fn converts_index_and_name_back_and_forth() {
+        let width_field: usize = 30;
+        for idx_cell in 0..width_field * 30 {
+            let coordinates = Coordinates::from_index(idx_cell, width_field);
+            assert_eq!(Coordinates::from_a1(coordinates.to_a1().as_str()), Some(coordinates));
+            assert_eq!(coordinates.to_index(width_field), idx_cell);
+        }
+        assert_eq!(Coordinates::from_index(27, 28).to_a1(), "AB1");
+    }
+}
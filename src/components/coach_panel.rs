@@ -0,0 +1,144 @@
+use yew::{Component, ComponentLink, Html, html, classes, Properties};
+use crate::components::cell::get_cell_name;
+use crate::i18n::{tr, trf};
+use crate::objects::coach::{self, Grade, ShotRating};
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct CoachPanelProps {
+    // rating: The rating of the last human shot, None before the first one.
+    pub rating: Option<ShotRating>,
+    // names_players: The names of all players in order.
+    pub names_players: Vec<String>,
+    pub width_field: usize,
+}
+
+/// The rating of the last shot of a human player during the game.
+pub struct CoachPanel {
+    props: CoachPanelProps,
+}
+
+impl Component for CoachPanel {
+    type Message = ();
+    type Properties = CoachPanelProps;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> bool {
+        if self.props == props {
+            return false;
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let rating: &ShotRating = match &self.props.rating {
+            None => { return html! {}; }
+            Some(rating) => { rating }
+        };
+        let name_player: &str = self.props.names_players.get(rating.idx_player).map_or("", |name| name.as_str());
+        html! {
+            <p class=classes!("coach", rating.grade.get_class())>
+                { format!(
+                    "{}: {} {}",
+                    name_player,
+                    get_cell_name(rating.idx_cell, self.props.width_field),
+                    get_rating_text(rating, self.props.width_field),
+                ) }
+            </p>
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct CoachSummaryProps {
+    pub idx_player: usize,
+    // ratings: The ratings of the shots of all human players in the game.
+    pub ratings: Vec<ShotRating>,
+    // names_players: The names of all players in order.
+    pub names_players: Vec<String>,
+    pub width_field: usize,
+}
+
+/// The numbers of graded shots and the biggest mistakes of the player after the game.
+pub struct CoachSummary {
+    props: CoachSummaryProps,
+}
+
+impl Component for CoachSummary {
+    type Message = ();
+    type Properties = CoachSummaryProps;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> bool {
+        if self.props == props {
+            return false;
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let idx_player: usize = self.props.idx_player;
+        let width_field: usize = self.props.width_field;
+        let get_name = |idx: usize| self.props.names_players.get(idx).cloned().unwrap_or_default();
+        let count_grade = |grade: Grade| self.props.ratings.iter()
+            .filter(|rating| rating.idx_player == idx_player && rating.grade == grade)
+            .count();
+        let mistakes = coach::get_biggest_mistakes(&self.props.ratings, idx_player, 3);
+        html! {
+            <div class="coach-summary">
+                <h2>{ trf("coach.title", &[get_name(idx_player).as_str()]) }</h2>
+                <p>
+                    { trf("coach.summary", &[
+                        count_grade(Grade::Good).to_string().as_str(),
+                        count_grade(Grade::Inaccuracy).to_string().as_str(),
+                        count_grade(Grade::Blunder).to_string().as_str(),
+                    ]) }
+                </p>
+                <ul>
+                    { for mistakes.iter().map(|rating| html! {
+                        <li>
+                            { trf("coach.mistake", &[
+                                get_cell_name(rating.idx_cell, width_field).as_str(),
+                                get_name(rating.idx_target).as_str(),
+                                get_rating_text(rating, width_field).as_str(),
+                            ]) }
+                        </li>
+                    }) }
+                </ul>
+            </div>
+        }
+    }
+}
+
+/// Returns the grade of the shot with the probabilities, for example
+/// "inaccuracy, 12% (best 34% at E7)".
+fn get_rating_text(rating: &ShotRating, width_field: usize) -> String {
+    let text_grade: String = format!(
+        "{}, {:.0}%",
+        tr(format!("coach.{}", rating.grade.get_name()).as_str()),
+        rating.probability * 100.0,
+    );
+    if rating.grade == Grade::Good {
+        return text_grade;
+    }
+    trf("coach.best", &[
+        text_grade.as_str(),
+        format!("{:.0}%", rating.best_probability * 100.0).as_str(),
+        get_cell_name(rating.idx_best_cell, width_field).as_str(),
+    ])
+}
@@ -1,20 +1,50 @@
 use yew::{ html, Component, ComponentLink, Html, ShouldRender, Properties, Callback};
-use crate::components::cell::PlayCell;
+use crate::components::cell::{PlayCell, Coordinates, Outline, get_column_name};
+use crate::objects::game::Annotation;
 
 pub struct Callbacks {
     on_click_cell: Callback<usize>,
+    on_annotate_cell: Callback<usize>,
 }
 
 #[derive(Clone, Debug, PartialEq, Properties)]
 pub struct PlayFieldProps {
     pub field_number: usize,
+    pub width_field: usize,
+    pub height_field: usize,
     pub is_interactive: bool,
     pub is_fog_of_war: bool,
     pub parent_call: Callback<(usize, usize)>,
+    // heatmap: For each cell, the probability that it contains a deck, shown over the unknown cells.
+    #[prop_or_default]
+    pub heatmap: Option<Vec<f64>>,
+    // labels: For each cell, the text shown over it, for example the order number of the shot.
+    #[prop_or_default]
+    pub labels: Vec<String>,
+    // highlighted_cell: The index of the cell pointed at from outside the field.
+    #[prop_or_default]
+    pub highlighted_cell: Option<usize>,
+    // cursor_cell: The index of the cell with the keyboard cursor.
+    #[prop_or_default]
+    pub cursor_cell: Option<usize>,
+    // caption: The name of the field announced by screen readers.
+    #[prop_or_default]
+    pub caption: String,
+    // sunk_cells: For each cell, "True" if it is a deck of a sunk ship.
+    #[prop_or_default]
+    pub sunk_cells: Vec<bool>,
+    // annotations: For each cell, the mark put on it by the player.
+    #[prop_or_default]
+    pub annotations: Vec<Option<Annotation>>,
+    // annotate_call: Called with the cell and the field to change the mark of the cell,
+    //                None if the cells cannot be marked.
+    #[prop_or_default]
+    pub annotate_call: Option<Callback<(usize, usize)>>,
 }
 
 pub enum Msg {
     ClickField(usize),
+    AnnotateField(usize),
 }
 
 pub struct PlayField {
@@ -31,6 +61,7 @@ impl Component for PlayField {
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
         let callbacks = Callbacks {
             on_click_cell: link.callback(|cell| Msg::ClickField(cell)),
+            on_annotate_cell: link.callback(Msg::AnnotateField),
         };
 
         Self {
@@ -47,6 +78,12 @@ impl Component for PlayField {
                 self.props.parent_call.emit((idx, self.props.field_number));
                 false
             }
+            Msg::AnnotateField(idx) => {
+                if let Some(annotate_call) = &self.props.annotate_call {
+                    annotate_call.emit((idx, self.props.field_number));
+                }
+                false
+            }
         }
     }
 
@@ -56,11 +93,19 @@ impl Component for PlayField {
     }
 
     fn view(&self) -> Html {
-        let width_field: usize = 10;
-        let height_field: usize = 10;
+        let width_field: usize = self.props.width_field;
+        let height_field: usize = self.props.height_field;
+        // The most probable cell is highlighted the most.
+        let max_probability: f64 = self.props.heatmap.as_ref()
+            .map_or(0.0, |heatmap| heatmap.iter().cloned().fold(0.0, f64::max));
+        let get_intensity = |idx_cell: usize| -> Option<f64> {
+            let probability: f64 = *self.props.heatmap.as_ref()?.get(idx_cell)?;
+            if max_probability > 0.0 { Some(probability / max_probability) } else { Some(0.0) }
+        };
         let cell_rows = (0..height_field).into_iter().map(|idx_row| {
             html! {
-                <div key=idx_row class="game-row">
+                <div key=idx_row class="game-row" role="row">
+                    <span class="field-label" role="rowheader">{ idx_row + 1 }</span>
                     {
                         for (0..width_field).into_iter().map(|idx_col| {
                         html! {
@@ -70,6 +115,16 @@ impl Component for PlayField {
                             is_fog_of_war=self.props.is_fog_of_war
                             is_interactive=self.props.is_interactive
                             msg_click_cell=self.callbacks.on_click_cell.clone()
+                            intensity=get_intensity(idx_row * width_field + idx_col)
+                            label=self.props.labels.get(idx_row * width_field + idx_col)
+                                .filter(|label| !label.is_empty())
+                                .cloned()
+                            is_highlighted=self.props.highlighted_cell == Some(idx_row * width_field + idx_col)
+                            is_cursor=self.props.cursor_cell == Some(idx_row * width_field + idx_col)
+                            name=Coordinates { x: idx_col, y: idx_row }.to_a1()
+                            outline=self.get_outline(idx_col, idx_row)
+                            annotation=self.props.annotations.get(idx_row * width_field + idx_col).cloned().flatten()
+                            msg_annotate_cell=self.props.annotate_call.as_ref().map(|_| self.callbacks.on_annotate_cell.clone())
                             />
                         }
                     })
@@ -77,12 +132,39 @@ impl Component for PlayField {
                 </div>
             }
         });
+        // The columns are named by letters and the rows by numbers, as in "C5".
+        let column_labels = (0..width_field).map(|idx_col| html! {
+            <span class="field-label" role="columnheader">{ get_column_name(idx_col) }</span>
+        });
         html! {
-            <div class="game-field">
+            <div class="game-field" role="grid" aria-label=self.props.caption.clone()>
+                <div class="game-row" role="row">
+                    <span class="field-label" role="columnheader"></span>
+                    { for column_labels }
+                </div>
                 { for cell_rows }
             </div>
         }
     }
+}
 
+impl PlayField {
+    fn is_sunk(&self, idx_col: usize, idx_row: usize) -> bool {
+        idx_col < self.props.width_field && idx_row < self.props.height_field
+            && self.props.sunk_cells.get(idx_row * self.props.width_field + idx_col).cloned().unwrap_or(false)
+    }
 
+    /// Returns the sides of the cell that border on cells outside the sunk ship,
+    /// so that the decks of the ship are outlined as one shape.
+    fn get_outline(&self, idx_col: usize, idx_row: usize) -> Outline {
+        if !self.is_sunk(idx_col, idx_row) {
+            return Outline::default();
+        }
+        Outline {
+            top: idx_row == 0 || !self.is_sunk(idx_col, idx_row - 1),
+            right: !self.is_sunk(idx_col + 1, idx_row),
+            bottom: !self.is_sunk(idx_col, idx_row + 1),
+            left: idx_col == 0 || !self.is_sunk(idx_col - 1, idx_row),
+        }
+    }
 }
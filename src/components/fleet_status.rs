@@ -0,0 +1,95 @@
+use yew::{Component, ComponentLink, Html, html, classes, Properties};
+use crate::i18n::{tr, trf};
+
+/// What is known about a ship of the fleet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShipStatus {
+    pub name: String,
+    pub size: usize,
+    // num_hits: How many decks of the ship are knocked out.
+    pub num_hits: usize,
+    pub is_sunk: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct FleetStatusProps {
+    pub ships: Vec<ShipStatus>,
+    // is_fog_of_war: If "True", then only the sizes of the ships still afloat are shown.
+    pub is_fog_of_war: bool,
+}
+
+/// The list of the ships of the player with their damage.
+pub struct FleetStatus {
+    props: FleetStatusProps,
+}
+
+impl Component for FleetStatus {
+    type Message = ();
+    type Properties = FleetStatusProps;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> bool {
+        if self.props == props {
+            return false;
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        if self.props.is_fog_of_war {
+            return self.view_remaining_sizes();
+        }
+        html! {
+            <div class="fleet-status">
+                <h2>{ tr("fleet.title") }</h2>
+                <ul>
+                    { for self.props.ships.iter().map(|ship| {
+                        let class_sunk: Option<&str> = if ship.is_sunk { Some("sunk") } else { None };
+                        let text_damage: String = if ship.is_sunk {
+                            tr("fleet.sunk")
+                        } else {
+                            trf("fleet.hits", &[ship.num_hits.to_string().as_str(), ship.size.to_string().as_str()])
+                        };
+                        // Every deck of the ship is drawn as a square.
+                        let decks: String = "\u{25a0}".repeat(ship.size);
+                        html! {
+                            <li class=classes!("fleet-ship", class_sunk)>
+                                <span class="fleet-decks">{ decks }</span>
+                                { format!(" {}: {}", ship.name, text_damage) }
+                            </li>
+                        }
+                    }) }
+                </ul>
+            </div>
+        }
+    }
+}
+
+impl FleetStatus {
+    /// Renders the sizes of the ships that are not sunk yet, from the largest.
+    fn view_remaining_sizes(&self) -> Html {
+        let mut sizes: Vec<usize> = self.props.ships.iter()
+            .filter(|ship| !ship.is_sunk)
+            .map(|ship| ship.size)
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        let text_sizes: String = sizes.iter()
+            .map(|size| size.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        html! {
+            <div class="fleet-status">
+                <h2>{ tr("fleet.title") }</h2>
+                <p>{ trf("fleet.remaining", &[if text_sizes.is_empty() { "-" } else { text_sizes.as_str() }]) }</p>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use yew::{Component, ComponentLink, Html, html, classes, Callback, Properties};
+use crate::i18n::{tr, trf};
+
+/// A shot in the history of the game, as it is shown in the list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryShot {
+    // idx_player: The index of the player who shot, the turn changes with it.
+    pub idx_player: usize,
+    pub name_player: String,
+    pub name_target: String,
+    // cell: The name of the cell in A1 notation.
+    pub cell: String,
+    pub result: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct HistoryPanelProps {
+    pub shots: Vec<HistoryShot>,
+    // review_shot: The index of the last shot shown on the fields, None for the final position.
+    pub review_shot: Option<usize>,
+    pub is_over: bool,
+    // is_moving_fleet: If "True", then the review notes that the ships are at their final positions.
+    pub is_moving_fleet: bool,
+    // hover_call: Called with the shot under the pointer, or None when it leaves the list.
+    pub hover_call: Callback<Option<usize>>,
+    // jump_call: Called with the shot to review, or None to return to the final position.
+    pub jump_call: Callback<Option<usize>>,
+}
+
+/// The list of the shots in order with the changes of turn.
+/// After the game, a click on a shot shows the fields as they were after it,
+/// with the moving ships at their final positions.
+pub struct HistoryPanel {
+    props: HistoryPanelProps,
+}
+
+impl Component for HistoryPanel {
+    type Message = ();
+    type Properties = HistoryPanelProps;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> bool {
+        if self.props == props {
+            return false;
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let shots: &Vec<HistoryShot> = &self.props.shots;
+        let entries = shots.iter().enumerate().map(|(idx_shot, shot)| {
+            let turn_change: Html = match idx_shot.checked_sub(1).and_then(|idx| shots.get(idx)) {
+                Some(previous) if previous.idx_player != shot.idx_player => {
+                    html! { <li class="history-turn">{ trf("board.turn", &[shot.name_player.as_str()]) }</li> }
+                }
+                _ => { html! {} }
+            };
+            let class_reviewed: Option<&str> = if self.props.review_shot == Some(idx_shot) { Some("reviewed") } else { None };
+            html! {
+                <>
+                { turn_change }
+                <li class=classes!("history-shot", class_reviewed)
+                    onmouseover=self.props.hover_call.reform(move |_| Some(idx_shot))
+                    onmouseout=self.props.hover_call.reform(|_| None)
+                    onclick=self.props.jump_call.reform(move |_| Some(idx_shot))>
+                    { format!(
+                        "{}. {} \u{2192} {}: {} {}",
+                        idx_shot + 1,
+                        shot.name_player,
+                        shot.name_target,
+                        shot.cell,
+                        shot.result,
+                    ) }
+                </li>
+                </>
+            }
+        });
+        let final_button: Html = if self.props.is_over && self.props.review_shot.is_some() {
+            html! {
+                <button onclick=self.props.jump_call.reform(|_| None)>{ tr("history.final") }</button>
+            }
+        } else { html! {} };
+        // The ships move during the game, but only their last positions are known.
+        let final_ships_note: Html = if self.props.is_over && self.props.is_moving_fleet {
+            html! { <p class="history-note">{ tr("history.final_ships") }</p> }
+        } else { html! {} };
+        html! {
+            <div class="board history">
+                <h1 class="title">{ tr("history.title") }</h1>
+                { final_button }
+                { final_ships_note }
+                <ol>
+                    { for entries }
+                </ol>
+            </div>
+        }
+    }
+}
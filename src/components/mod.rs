@@ -1,4 +1,13 @@
 pub mod cell;
 pub mod field;
 pub mod play_board;
-
+pub mod solo_board;
+pub mod puzzle_board;
+pub mod stats_page;
+pub mod achievements_panel;
+pub mod toast;
+pub mod modal;
+pub mod fleet_status;
+pub mod coach_panel;
+pub mod analysis_view;
+pub mod history_panel;
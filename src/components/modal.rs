@@ -0,0 +1,44 @@
+use yew::{Component, ComponentLink, Html, html, Children, Properties};
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct ModalProps {
+    pub title: String,
+    // children: The content of the window, for example the buttons of the actions.
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// The window over the page that waits for the choice of the player
+/// without blocking the browser.
+pub struct Modal {
+    props: ModalProps,
+}
+
+impl Component for Modal {
+    type Message = ();
+    type Properties = ModalProps;
+
+    fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> bool {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="modal-backdrop">
+                <div class="modal" role="dialog" aria-modal="true" aria-label=self.props.title.clone()>
+                    <h1 class="title">{ self.props.title.clone() }</h1>
+                    { self.props.children.clone() }
+                </div>
+            </div>
+        }
+    }
+}
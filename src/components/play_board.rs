@@ -1,42 +1,411 @@
-use yew::{ComponentLink, Component, Html, html, Callback, Dispatched};
+use yew::{ComponentLink, Component, Html, html, Callback, Dispatched, classes, ChangeData, FocusEvent, KeyboardEvent};
 use crate::components::field::PlayField;
+use crate::components::toast::{Toast, ToastKind, ToastList};
+use crate::components::modal::Modal;
+use crate::components::fleet_status::{FleetStatus, ShipStatus};
+use crate::components::cell::{Coordinates, get_cell_element_id, get_cell_name};
+use crate::components::coach_panel::CoachPanel;
+use crate::components::analysis_view::AnalysisView;
+use crate::components::history_panel::{HistoryPanel, HistoryShot};
 use yew::agent::Dispatcher;
+use yew::services::ConsoleService;
 use crate::agents::agent_w;
+use crate::agents::event_bus::{self, EventBus, GameEvent};
 use crate::settings;
+use crate::i18n::{tr, trf, tr_count};
 use crate::objects::player;
-use yew::services::DialogService;
+use crate::objects::game::{Game, AutoMove, ShotRecord, ShotResult, Annotation};
+use crate::objects::ship::Maneuver;
+use crate::objects::coach;
+use crate::objects::analysis;
+use crate::objects::profile;
+use crate::objects::rating;
+use crate::objects::daily::get_today;
 
+// BOARD_NAME: The name of the board in the events on the event bus.
+pub const BOARD_NAME: &str = "battle";
+// MAX_TOASTS: How many last notifications are kept.
+const MAX_TOASTS: usize = 3;
+// LARGE_FIELD_SIZE: Fields with more rows or columns than this can be zoomed.
+const LARGE_FIELD_SIZE: usize = 10;
+// ZOOM_STEP, MIN_ZOOM, MAX_ZOOM: The scale of the fields in percent.
+const ZOOM_STEP: usize = 25;
+const MIN_ZOOM: usize = 50;
+const MAX_ZOOM: usize = 200;
 
 pub enum Msg {
+    // Shot at the cell with the index on the field of the player with the index.
     SwitchedCellWithIndex((usize, usize)),
+    // Changing the mark of the cell of the opponent's field without shooting.
+    AnnotatedCellWithIndex((usize, usize)),
+    // Selecting a ship of the player whose turn it is to maneuver
+    // (only in the "moving fleet" variant).
+    SelectedShipWithIndex((usize, usize)),
+    ManeuveredShip(Maneuver),
+    // The move of the computer player whose turn it is.
+    AutoMove,
+    // Showing or hiding the probabilities of decks on the opponents' fields.
+    HeatmapToggled,
+    // The cell to shoot at typed in A1 notation.
+    CoordinateEntered(ChangeData),
+    // The key pressed while the board has the focus.
+    KeyPressed(KeyboardEvent),
+    // The board got ("True") or lost the focus.
+    FocusChanged(bool),
+    // Pointing at the shot with the index in the history, or leaving the history.
+    HistoryHovered(Option<usize>),
+    // Showing the fields after the shot with the index when reviewing a finished game,
+    // or the final position.
+    HistoryJumped(Option<usize>),
     EndGame(String),
+    // Showing the field of the player with the index on a narrow screen.
+    FieldTabSelected(usize),
+    // Making the fields larger ("True") or smaller.
+    Zoomed(bool),
+    // Starting the same game anew from the game-over window.
+    Rematch,
+    // Closing the game-over window to review the game.
+    GameOverClosed,
 }
 
 pub struct PlayBoard {
     link: ComponentLink<Self>,
     props: settings::SettingsProps,
-    pub first_player: player::Player,
-    pub second_player: player::Player,
-    // If "True", then the first player turn, otherwise - the second
-    pub whose_move: bool,
+    pub game: Game,
     pub event_work: Dispatcher<agent_w::Worker>,
-
+    // event_bus: Publishes the events of the game for the subscribed panels.
+    event_bus: Dispatcher<EventBus>,
+    // The index of the ship of the player whose turn it is chosen for the maneuver.
+    selected_ship: Option<usize>,
+    // viewer: The index of the human player whose fleet is revealed on the screen,
+    // or None if all players are controlled by the computer.
+    viewer: Option<usize>,
+    // is_heatmap_shown: "True" if the probabilities of decks are shown on the opponents' fields.
+    is_heatmap_shown: bool,
+    // shot_ratings: The ratings of the shots of the human players in order.
+    shot_ratings: Vec<coach::ShotRating>,
+    // winners: The names of the winners, set when the game is over.
+    winners: Option<String>,
+    // start_time: The time of the start of the game in milliseconds.
+    start_time: f64,
+    // hovered_shot: The index of the shot pointed at in the history.
+    hovered_shot: Option<usize>,
+    // review_shot: The index of the last shot shown on the fields when reviewing a finished game,
+    // or None if the final position is shown.
+    review_shot: Option<usize>,
+    // cursor: The index of the field and the index of the cell with the keyboard cursor.
+    cursor: (usize, usize),
+    // typed_text: The letters and digits of the coordinate typed on the keyboard so far.
+    typed_text: String,
+    // is_focused: "True" if the board has the keyboard focus, then the cursor is shown.
+    is_focused: bool,
+    // toasts: The last notifications about the game.
+    toasts: Vec<Toast>,
+    // previous_turn: The index of the player who moved before the current move.
+    previous_turn: usize,
+    // is_game_over_shown: "True" while the game-over window is open.
+    is_game_over_shown: bool,
+    // zoom: The scale of the fields in percent.
+    zoom: usize,
 }
 
 impl PlayBoard {
+    /// Returns "True" if the player with the index is controlled manually.
+    fn is_manual_control(&self, idx_player: usize) -> bool {
+        self.props.players_settings.get(idx_player)
+            .map_or(false, |player_settings| player_settings.is_manual_control)
+    }
+
+    /// Returns the size of the ship with the index in the fleet of the player.
+    fn get_ship_size(&self, idx_player: usize, idx_ship: usize) -> usize {
+        self.game.get_player(idx_player)
+            .and_then(|player| player.get_ships_as_iter().nth(idx_ship))
+            .map_or(0, |ship| ship.get_size())
+    }
+
+    /// Returns the name of the player with the index.
+    fn get_player_name(&self, idx_player: usize) -> String {
+        self.game.get_player(idx_player)
+            .map_or(String::new(), |player| player.get_name().to_string())
+    }
+
+    /// Returns the names of all players in order.
+    fn get_player_names(&self) -> Vec<String> {
+        (0..self.game.get_num_players()).map(|idx_player| self.get_player_name(idx_player)).collect()
+    }
+
     /// Returns "True" if there is a winner and "lies" if there is no winner.
     fn check_winner(&self) -> bool {
-        if self.first_player.get_num_living_ships() == 0 {
-            self.link
-                .callback(Msg::EndGame)
-                .emit(self.second_player.get_name().to_string());
-            return true;
-        } else if self.second_player.get_num_living_ships() == 0 {
-            self.link
-                .callback(Msg::EndGame)
-                .emit(self.first_player.get_name().to_string());
+        let winners: Vec<usize> = self.game.get_winners();
+        if winners.is_empty() {
+            return false;
+        }
+        let names_winners: Vec<String> = winners.iter()
+            .map(|&idx_winner| self.get_player_name(idx_winner))
+            .collect();
+        self.link
+            .callback(Msg::EndGame)
+            .emit(names_winners.join(" & "));
+        true
+    }
+
+    /// The player whose turn it is shoots at the field of the target player,
+    /// the cells of the field are updated.
+    /// Returns "True" if the shot was allowed.
+    fn shoot(&mut self, idx_target: usize, idx_cell: usize) -> bool {
+        let idx_player: usize = self.game.get_whose_move();
+        let outcome = match self.game.shoot(idx_target, idx_cell) {
+            None => { return false; }
+            Some(outcome) => { outcome }
+        };
+        self.publish_shot_events(idx_player, idx_target, idx_cell, outcome.result);
+        if let ShotResult::Sunk(idx_ship) = outcome.result {
+            let kind: ToastKind = if self.is_manual_control(idx_player) { ToastKind::Success } else { ToastKind::Info };
+            self.notify(trf("toast.sunk", &[
+                self.get_player_name(idx_player).as_str(),
+                self.get_ship_size(idx_target, idx_ship).to_string().as_str(),
+                self.get_player_name(idx_target).as_str(),
+            ]), kind);
+        }
+        // If the shot turned out to be fatal, then the whole ship is shown as sunk
+        // and the adjacent cells that were not shot as water.
+        if let ShotResult::Sunk(idx_ship) = outcome.result {
+            let area_ship: Vec<usize> = self.game.get_player(idx_target)
+                .and_then(|target| target.get_ships_as_iter().nth(idx_ship))
+                .map_or(Vec::new(), |ship| ship.get_area_ship());
+            for idx in area_ship {
+                self.event_work.send(agent_w::Request::GetStateCell(idx, idx_target, "Sunk".to_string()))
+            }
+            for idx in outcome.area_near_ship {
+                if !self.game.get_player(idx_target).map_or(false, |target| target.is_cell_shot(idx)) {
+                    self.event_work.send(agent_w::Request::GetStateCell(idx, idx_target, "Water".to_string()))
+                }
+            }
+        } else {
+            self.event_work.send(agent_w::Request::Shot(idx_cell, idx_target));
+        }
+        true
+    }
+
+    /// Publishes the shot of the player and what it led to.
+    fn publish_shot_events(&mut self, idx_player: usize, idx_target: usize, idx_cell: usize, result: ShotResult) {
+        let is_hit: bool = result != ShotResult::Miss;
+        self.publish(GameEvent::ShotFired { idx_player, idx_target, idx_cell, is_hit });
+        if let ShotResult::Sunk(idx_ship) = result {
+            let size: usize = self.get_ship_size(idx_target, idx_ship);
+            let shots = self.game.get_shots();
+            let num_shots: usize = analysis::count_shots_to_sink(shots, shots.len() - 1);
+            self.publish(GameEvent::ShipSunk { idx_player, idx_target, size, num_shots });
+        }
+        let length: usize = self.game.get_hit_streak(idx_player);
+        if is_hit && length > 1 {
+            self.publish(GameEvent::HitStreak { idx_player, length });
+        }
+    }
+
+    fn publish(&mut self, event: GameEvent) {
+        self.event_bus.send(event_bus::Request::Publish(BOARD_NAME.to_string(), event));
+    }
+
+    /// Performs the maneuver of the ship of the player whose turn it is
+    /// and updates the cells of his field.
+    /// Returns "True" if the maneuver was allowed.
+    fn maneuver_ship(&mut self, idx_ship: usize, maneuver: Maneuver) -> bool {
+        let field_number: usize = self.game.get_whose_move();
+        let outcome = match self.game.maneuver(idx_ship, maneuver) {
+            None => { return false; }
+            Some(outcome) => { outcome }
+        };
+        for idx in outcome.old_area {
+            self.event_work.send(agent_w::Request::GetStateCell(idx, field_number, "Blank".to_string()))
+        }
+        for idx in outcome.new_area {
+            self.event_work.send(agent_w::Request::GetStateCell(idx, field_number, "Deck".to_string()))
+        }
+        // The opponents can no longer trust their misses.
+        let player: Option<&player::Player> = self.game.get_player(field_number);
+        for idx in outcome.stale_cells {
+            let is_deck = player.map_or(false, |player| player.get_ship_idx_by_cell(idx).is_some());
+            let text_state = if is_deck { "Deck" } else { "Blank" };
+            self.event_work.send(agent_w::Request::GetStateCell(idx, field_number, text_state.to_string()))
+        }
+        true
+    }
+
+    /// Returns the field the player whose turn it is shoots at by default:
+    /// the last target while it is in the game, otherwise the first opponent.
+    fn get_default_target(&self) -> Option<usize> {
+        let whose_move: usize = self.game.get_whose_move();
+        let alive_opponents: Vec<usize> = self.game.get_alive_opponents(whose_move);
+        self.game.get_last_target(whose_move)
+            .filter(|idx| alive_opponents.contains(idx))
+            .or_else(|| alive_opponents.first().cloned())
+    }
+
+    /// Moves the cursor, fires, moves the selected ship, starts a new game
+    /// or collects the typed coordinate by the key.
+    /// Returns "True" if the key was used.
+    fn handle_key(&mut self, key: &str, is_shift: bool) -> bool {
+        let width_field: usize = self.props.field_setting.width_field;
+        let height_field: usize = self.props.field_setting.height_field;
+        let (idx_field, idx_cell) = self.cursor;
+        let (x, y) = (idx_cell % width_field, idx_cell / width_field);
+        // The arrows with Shift move the selected ship instead of the cursor.
+        if is_shift {
+            let maneuver: Maneuver = match key {
+                "ArrowLeft" => { Maneuver::Left }
+                "ArrowRight" => { Maneuver::Right }
+                "ArrowUp" => { Maneuver::Up }
+                "ArrowDown" => { Maneuver::Down }
+                _ => { return false; }
+            };
+            self.link.send_message(Msg::ManeuveredShip(maneuver));
             return true;
-        } else { return false; }
+        }
+        match key {
+            "ArrowLeft" => { self.cursor.1 = y * width_field + x.saturating_sub(1); }
+            "ArrowRight" => { self.cursor.1 = y * width_field + (x + 1).min(width_field - 1); }
+            "ArrowUp" => { self.cursor.1 = y.saturating_sub(1) * width_field + x; }
+            "ArrowDown" => { self.cursor.1 = (y + 1).min(height_field - 1) * width_field + x; }
+            // The cursor goes over to the previous or the next field.
+            "PageUp" | "PageDown" => {
+                let num_players: usize = self.game.get_num_players();
+                let step: usize = if key == "PageUp" { num_players - 1 } else { 1 };
+                self.cursor.0 = (idx_field + step) % num_players;
+            }
+            "Enter" | " " => {
+                self.typed_text.clear();
+                // On his own field, the player selects a ship to maneuver.
+                let msg: Msg = if idx_field == self.game.get_whose_move() {
+                    Msg::SelectedShipWithIndex((idx_cell, idx_field))
+                } else {
+                    Msg::SwitchedCellWithIndex((idx_cell, idx_field))
+                };
+                self.link.send_message(msg);
+            }
+            "Escape" => { self.typed_text.clear(); }
+            "Backspace" => { self.typed_text.pop(); }
+            _ => {
+                let c: char = match key.chars().next() {
+                    Some(c) if key.chars().count() == 1 && c.is_ascii_alphanumeric() => { c.to_ascii_uppercase() }
+                    _ => { return false; }
+                };
+                // The commands give way to the letters of the columns on wide fields.
+                let is_column: bool = c.is_ascii_alphabetic() && ((c as u8 - b'A') as usize) < width_field;
+                if self.typed_text.is_empty() && !is_column && c == 'R' {
+                    self.link.send_message(Msg::ManeuveredShip(Maneuver::Rotate));
+                    return true;
+                }
+                // A game in progress is not thrown away by a stray key.
+                if self.typed_text.is_empty() && !is_column && c == 'N' && self.game.is_over() {
+                    self.restart();
+                    return true;
+                }
+                // Letters after digits begin a new coordinate.
+                if c.is_ascii_alphabetic() && self.typed_text.ends_with(|last: char| last.is_ascii_digit()) {
+                    self.typed_text.clear();
+                }
+                self.typed_text.push(c);
+                // The cursor follows the typed coordinate, Enter fires at it.
+                if let Some(coordinates) = Coordinates::from_a1(self.typed_text.as_str()) {
+                    if coordinates.x < width_field && coordinates.y < height_field {
+                        self.cursor.1 = coordinates.to_index(width_field);
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Starts the game anew with the same settings.
+    fn restart(&self) {
+        self.props.parent_call.emit((
+            self.props.players_settings.clone(),
+            self.props.field_setting.clone(),
+            self.props.rules_setting.clone(),
+        ));
+    }
+
+    /// Shows the notification, only the last ones are kept.
+    fn notify(&mut self, text: String, kind: ToastKind) {
+        let id: usize = self.toasts.last().map_or(0, |toast| toast.id + 1);
+        self.toasts.push(Toast { id, text, kind });
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// Adds the finished game to the profiles of the human players.
+    fn record_games(&self) {
+        let winners: Vec<usize> = self.game.get_winners();
+        let seconds: u64 = ((js_sys::Date::now() - self.start_time) / 1000.0) as u64;
+        let rules: String = settings::get_rules_id(&self.props.field_setting, &self.props.rules_setting);
+        for (idx_player, player_settings) in self.props.players_settings.iter().enumerate() {
+            if !player_settings.is_manual_control {
+                continue;
+            }
+            // The game is counted against the strongest computer opponent.
+            let opponent: Option<player::AiLevel> = self.props.players_settings.iter().enumerate()
+                .filter(|&(idx_other, other)| !other.is_manual_control && self.game.is_opponent(idx_player, idx_other))
+                .filter_map(|(idx_other, _)| self.game.get_player(idx_other).map(|other| other.get_ai_level()))
+                .max_by_key(|&ai_level| ai_level as usize);
+            let stats: analysis::PlayerStats = analysis::get_player_stats(&self.game, idx_player);
+            profile::record_game(player_settings.name.as_str(), profile::GameRecord {
+                date: get_today(),
+                opponent,
+                rules: rules.clone(),
+                is_win: winners.contains(&idx_player),
+                shots: stats.num_shots,
+                hits: stats.num_hits,
+                seconds,
+            });
+        }
+        // Only games of two players are rated.
+        if self.game.get_num_players() == 2 && winners.len() == 1 {
+            let idx_winner: usize = winners[0];
+            if let Err(error) = rating::record_rated_game(&self.get_participant(idx_winner), &self.get_participant(1 - idx_winner)) {
+                ConsoleService::error(error.as_str());
+            }
+        }
+    }
+
+    /// Returns the player with the index as a side of a rated game.
+    fn get_participant(&self, idx_player: usize) -> rating::Participant {
+        if self.is_manual_control(idx_player) {
+            rating::Participant::Profile(self.get_player_name(idx_player))
+        } else {
+            let ai_level: player::AiLevel = self.game.get_player(idx_player)
+                .map_or(player::AiLevel::Hard, |player| player.get_ai_level());
+            rating::Participant::Computer(ai_level)
+        }
+    }
+
+    /// Prepares the next move: finishes the game,
+    /// or shows the fleet of the next human player, or lets the computer move.
+    fn after_move(&mut self) {
+        if self.check_winner() {
+            return;
+        }
+        let idx_player: usize = self.game.get_whose_move();
+        let is_turn_changed: bool = idx_player != self.previous_turn;
+        self.previous_turn = idx_player;
+        if self.is_manual_control(idx_player) {
+            if is_turn_changed {
+                self.notify(trf("board.turn", &[self.get_player_name(idx_player).as_str()]), ToastKind::Info);
+            }
+            if self.viewer != Some(idx_player) {
+                self.selected_ship = None;
+            }
+            self.viewer = Some(idx_player);
+            // The cursor moves to a field the player can shoot at.
+            if !self.game.get_alive_opponents(idx_player).contains(&self.cursor.0) {
+                if let Some(idx_target) = self.get_default_target() {
+                    self.cursor.0 = idx_target;
+                }
+            }
+        } else {
+            self.link.send_message(Msg::AutoMove);
+        }
     }
 }
 
@@ -45,24 +414,65 @@ impl Component for PlayBoard {
     type Properties = settings::SettingsProps;
 
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
-        let first_player: player::Player = player::Player::create(
-            props.first_player_settings.name.as_str(),
-            props.first_player_settings.is_manual_control,
-        );
-        let second_player: player::Player = player::Player::create(
-            props.second_player_settings.name.as_str(),
-            props.second_player_settings.is_manual_control,
-        );
+        let field_setting: &settings::FieldSettings = &props.field_setting;
+        // The level closest to the rating of the first human player, if it is picked automatically.
+        let auto_ai_level: Option<player::AiLevel> = if props.rules_setting.is_ai_level_auto {
+            props.players_settings.iter()
+                .find(|player_settings| player_settings.is_manual_control)
+                .map(|player_settings| rating::get_closest_ai_level(
+                    rating::get_rating(&rating::Participant::Profile(player_settings.name.clone()))
+                ))
+        } else {
+            None
+        };
+        let players: Vec<player::Player> = props.players_settings.iter()
+            .map(|player_settings| {
+                let mut player = player::Player::create(
+                    player_settings.name.as_str(),
+                    player_settings.is_manual_control,
+                    field_setting.width_field,
+                    field_setting.height_field,
+                    &field_setting.fleet,
+                    None,
+                );
+                player.set_ai_level(auto_ai_level.unwrap_or(player_settings.ai_level));
+                player
+            })
+            .collect();
+        let teams: Vec<Option<usize>> = props.players_settings.iter()
+            .map(|player_settings| player_settings.team)
+            .collect();
+        let game = Game::create(players, &teams, props.rules_setting.is_moving_fleet);
+        let viewer: Option<usize> = props.players_settings.iter()
+            .position(|player_settings| player_settings.is_manual_control);
 
         let event_work = agent_w::Worker::dispatcher();
+        let event_bus = EventBus::dispatcher();
+        let idx_cursor_field: usize = viewer
+            .and_then(|idx_viewer| game.get_alive_opponents(idx_viewer).first().cloned())
+            .unwrap_or(0);
 
         Self {
             link,
             props,
-            first_player,
-            second_player,
-            whose_move: true,
+            game,
             event_work,
+            event_bus,
+            selected_ship: None,
+            viewer,
+            is_heatmap_shown: false,
+            shot_ratings: Vec::new(),
+            winners: None,
+            start_time: js_sys::Date::now(),
+            hovered_shot: None,
+            review_shot: None,
+            cursor: (idx_cursor_field, 0),
+            typed_text: String::new(),
+            is_focused: false,
+            toasts: Vec::new(),
+            previous_turn: 0,
+            is_game_over_shown: false,
+            zoom: 100,
         }
     }
 
@@ -70,61 +480,156 @@ impl Component for PlayBoard {
         match msg {
             // Processes the message from the field when it is clicked.
             Msg::SwitchedCellWithIndex((idx_cell, field_number)) => {
-                // We ignore messages by fields in the wrong turn.
-                if (self.whose_move && field_number == 1) || (!self.whose_move && field_number == 2) {
+                // We ignore clicks while the computer is moving.
+                let idx_player: usize = self.game.get_whose_move();
+                if !self.is_manual_control(idx_player) {
+                    return false;
+                }
+                // The shot is rated by what was known before it.
+                let probability_map: Vec<f64> = self.game.get_player(field_number)
+                    .map_or(Vec::new(), |target| target.get_probability_map());
+                if !self.shoot(field_number, idx_cell) {
+                    self.notify(tr("toast.cannot_shoot"), ToastKind::Error);
+                    return true;
+                }
+                self.shot_ratings.push(coach::rate_shot(idx_player, field_number, idx_cell, &probability_map));
+                self.after_move();
+                true
+            }
+            Msg::AnnotatedCellWithIndex((idx_cell, field_number)) => {
+                self.game.annotate(field_number, idx_cell)
+            }
+            Msg::SelectedShipWithIndex((idx_cell, field_number)) => {
+                if field_number != self.game.get_whose_move() {
+                    return false;
+                }
+                self.selected_ship = self.game.get_player(field_number)
+                    .and_then(|player| player.get_undamaged_ship_idx_by_cell(idx_cell));
+                true
+            }
+            Msg::ManeuveredShip(maneuver) => {
+                if !self.is_manual_control(self.game.get_whose_move()) {
                     return false;
                 }
-                match self.check_winner() {
-                    true => { return false; }
-                    false => {}
-                }
-                // If it is the turn of the 1st player,
-                // then we look at the location of the ships of the 2nd player.
-                let player: &mut player::Player = match self.whose_move {
-                    // true - First player turn
-                    true => { &mut self.second_player }
-                    // false - Second player turn
-                    false => { &mut self.first_player }
+                let idx_ship = match self.selected_ship {
+                    None => { return false; }
+                    Some(idx_ship) => { idx_ship }
                 };
-                // Perform a "shot" on the index.
-                let (number_ship, is_successful_shot, is_alive_ship) = player.process_a_shot(idx_cell);
-                // If the shot turned out to be fatal, then mark all adjacent cells as "Miss".
-                if !is_alive_ship {
-                    if number_ship.is_some() {
-                        let ship = player.get_ship_by_idx_as_mut_ref(number_ship.unwrap());
-                        if ship.is_some() {
-                            let area_near_ship = ship.unwrap().get_area_near_ship(None);
-                            for idx in area_near_ship {
-                                self.event_work.send(agent_w::Request::Shot(idx, field_number))
-                            }
-                        }
+                if !self.maneuver_ship(idx_ship, maneuver) {
+                    return false;
+                }
+                self.selected_ship = None;
+                // The maneuver replaces the shot, so the turn passes to the opponent.
+                self.after_move();
+                true
+            }
+            Msg::AutoMove => {
+                if self.is_manual_control(self.game.get_whose_move()) {
+                    return false;
+                }
+                match self.game.generate_auto_move() {
+                    None => { return false; }
+                    Some(AutoMove::Shot(idx_target, idx_cell)) => {
+                        self.shoot(idx_target, idx_cell);
+                    }
+                    Some(AutoMove::Maneuver(idx_ship, maneuver)) => {
+                        self.maneuver_ship(idx_ship, maneuver);
                     }
                 }
-                self.event_work.send(agent_w::Request::Shot(idx_cell, field_number));
-
-
-                if !is_successful_shot {
-                    // Perform a player change
-                    self.whose_move = !self.whose_move;
+                self.after_move();
+                true
+            }
+            Msg::HeatmapToggled => {
+                self.is_heatmap_shown = !self.is_heatmap_shown;
+                true
+            }
+            Msg::CoordinateEntered(e) => {
+                let text: String = match e {
+                    ChangeData::Value(text) => { text }
+                    _ => { return false; }
+                };
+                let field_setting: &settings::FieldSettings = &self.props.field_setting;
+                let coordinates: Coordinates = match Coordinates::from_a1(text.as_str()) {
+                    Some(coordinates) if coordinates.x < field_setting.width_field
+                        && coordinates.y < field_setting.height_field => { coordinates }
+                    _ => {
+                        self.notify(trf("toast.bad_cell", &[text.trim()]), ToastKind::Error);
+                        return true;
+                    }
+                };
+                let idx_target: usize = match self.get_default_target() {
+                    None => { return false; }
+                    Some(idx_target) => { idx_target }
+                };
+                let idx_cell: usize = coordinates.to_index(field_setting.width_field);
+                self.link.send_message(Msg::SwitchedCellWithIndex((idx_cell, idx_target)));
+                false
+            }
+            Msg::KeyPressed(e) => {
+                // The keys pressed on the buttons inside the board are left to the buttons.
+                if e.target() != e.current_target() {
+                    return false;
+                }
+                let is_handled: bool = self.handle_key(e.key().as_str(), e.shift_key());
+                // The arrows and the space should not scroll the page.
+                if is_handled {
+                    e.prevent_default();
+                }
+                is_handled
+            }
+            Msg::FieldTabSelected(idx_field) => {
+                self.cursor.0 = idx_field;
+                true
+            }
+            Msg::Zoomed(is_zoom_in) => {
+                self.zoom = if is_zoom_in {
+                    (self.zoom + ZOOM_STEP).min(MAX_ZOOM)
                 } else {
-                    match self.check_winner() {
-                        true => { return false; }
-                        false => {}
+                    self.zoom.saturating_sub(ZOOM_STEP).max(MIN_ZOOM)
+                };
+                true
+            }
+            Msg::FocusChanged(is_focused) => {
+                self.is_focused = is_focused;
+                true
+            }
+            Msg::HistoryHovered(hovered_shot) => {
+                self.hovered_shot = hovered_shot;
+                true
+            }
+            Msg::HistoryJumped(review_shot) => {
+                if !self.game.is_over() {
+                    return false;
+                }
+                let num_shots: usize = self.game.get_shots().len();
+                let old_states = self.get_cell_states(self.review_shot.map_or(num_shots, |idx| idx + 1));
+                let new_states = self.get_cell_states(review_shot.map_or(num_shots, |idx| idx + 1));
+                for (idx_player, (old_field, new_field)) in old_states.iter().zip(new_states.iter()).enumerate() {
+                    for (idx_cell, (old_state, new_state)) in old_field.iter().zip(new_field.iter()).enumerate() {
+                        if old_state != new_state {
+                            self.event_work.send(agent_w::Request::GetStateCell(idx_cell, idx_player, new_state.to_string()));
+                        }
                     }
                 }
-                // If it is the turn of the second = th player (computer),
-                // then we generate the parameters of the shot.
-                if !self.whose_move {
-                    let new_idx_cell: usize =
-                        self.first_player.generate_new_auto_shot_idx();
-                    self.link
-                        .callback(Msg::SwitchedCellWithIndex)
-                        .emit((new_idx_cell, 1));
+                self.review_shot = review_shot;
+                true
+            }
+            Msg::EndGame(names_winners) => {
+                if self.winners.is_none() {
+                    self.record_games();
+                    let winners: Vec<usize> = self.game.get_winners();
+                    self.publish(GameEvent::GameWon { winners });
                 }
+                self.winners = Some(names_winners);
+                self.is_game_over_shown = true;
+                true
+            }
+            Msg::Rematch => {
+                self.restart();
                 false
             }
-            Msg::EndGame(name_winner) => {
-                DialogService::alert(&*format!("{:?} winner!", name_winner));
+            Msg::GameOverClosed => {
+                self.is_game_over_shown = false;
                 true
             }
         }
@@ -136,57 +641,445 @@ impl Component for PlayBoard {
     }
 
     fn view(&self) -> Html {
-        let name_first_player = self.props.first_player_settings.name.to_string();
-        let name_second_player = self.props.second_player_settings.name.to_string();
-        let is_manual_control_first_player = self.props.first_player_settings.is_manual_control;
-        let parent_call: Callback<(usize, usize)> = self.link.callback(Msg::SwitchedCellWithIndex);
         html! {
             <>
-            <div class="wrapper">
-                <div>
-                    <h1 class="title">{ name_first_player + " Field" }</h1>
-                    <PlayField
-                        field_number=1
-                        is_fog_of_war=!is_manual_control_first_player
-                        is_interactive=false
-                        parent_call=parent_call.clone()
-                    />
-                </div>
-                <div>
-                    <h1 class="title">{ name_second_player + " Field" }</h1>
-                    <PlayField
-                        field_number=2
-                        is_fog_of_war=true
-                        is_interactive=is_manual_control_first_player
-                        parent_call=parent_call.clone()
-                    ></PlayField>
-                </div>
+            { self.view_board_options() }
+            <ToastList toasts=self.toasts.clone() />
+            { self.view_game_over() }
+            // The screen readers announce the results of the shots and the turns.
+            <p class="visually-hidden" aria-live="polite">{ self.get_announcement() }</p>
+            { self.view_field_tabs() }
+            <div class="wrapper keyboard-area" tabindex="0" role="application" aria-label=tr("board.game_boards")
+                style=format!("--zoom: {:.2}", self.zoom as f64 / 100.0)
+                aria-activedescendant=get_cell_element_id(self.cursor.0, self.cursor.1)
+                onkeydown=self.link.callback(Msg::KeyPressed)
+                onfocus=self.link.callback(|_: FocusEvent| Msg::FocusChanged(true))
+                onblur=self.link.callback(|_: FocusEvent| Msg::FocusChanged(false))>
+                { for (0..self.game.get_num_players()).map(|idx_player| self.view_player_field(idx_player)) }
+                { self.view_history() }
             </div>
+            {
+                if self.game.is_over() {
+                    self.view_analysis()
+                } else {
+                    html! {
+                        <CoachPanel rating=self.shot_ratings.last().cloned()
+                            names_players=self.get_player_names() width_field=self.props.field_setting.width_field />
+                    }
+                }
+            }
             </>
         }
     }
 
-
     fn rendered(&mut self, first_render: bool) {
         if first_render {
-            for i in 1..3 {
-                let ships_iter = if i == 1 {
-                    self.first_player.get_ships_as_iter()
-                } else {
-                    self.second_player.get_ships_as_iter()
-                };
-                for ship in ships_iter {
+            let players: Vec<(String, bool)> = self.props.players_settings.iter()
+                .map(|player_settings| (player_settings.name.clone(), player_settings.is_manual_control))
+                .collect();
+            self.publish(GameEvent::GameStarted { players });
+            for (idx_player, player) in self.game.get_players_as_iter().enumerate() {
+                for ship in player.get_ships_as_iter() {
                     for &idx_cell in ship.get_area_ship().iter() {
                         self.event_work.send(
                             agent_w::Request::GetStateCell(
                                 idx_cell,
-                                i,
+                                idx_player,
                                 "Deck".to_string(),
                             )
                         )
                     }
                 }
             }
+            // The first player may be a computer.
+            self.after_move();
+        }
+    }
+}
+
+impl PlayBoard {
+    /// Renders the field of the player with the title and the maneuver controls.
+    fn view_player_field(&self, idx_player: usize) -> Html {
+        let mut name_player = self.get_player_name(idx_player);
+        if !self.is_manual_control(idx_player) {
+            if let Some(player) = self.game.get_player(idx_player) {
+                name_player = format!("{} [{}]", name_player, player.get_ai_level().get_name());
+            }
+        }
+        if let Some(team) = self.props.players_settings.get(idx_player).and_then(|p| p.team) {
+            name_player = format!("{} ({})", name_player, settings::get_team_name(team));
+        }
+        let whose_move: usize = self.game.get_whose_move();
+        let is_human_turn: bool = self.is_manual_control(whose_move) && !self.game.is_over();
+        let is_alive: bool = self.game.is_player_alive(idx_player);
+        let is_own_field: bool = idx_player == whose_move;
+        let is_moving_fleet: bool = self.props.rules_setting.is_moving_fleet && is_human_turn && is_own_field;
+        // After the game, all fleets are revealed.
+        let is_fog_of_war: bool = match self.viewer {
+            Some(idx_viewer) if !self.game.is_over() => { self.game.is_opponent(idx_viewer, idx_player) }
+            _ => { false }
+        };
+        let is_interactive: bool = is_human_turn && is_alive &&
+            (is_moving_fleet || self.game.is_opponent(whose_move, idx_player));
+        let parent_call: Callback<(usize, usize)> = if is_own_field {
+            self.link.callback(Msg::SelectedShipWithIndex)
+        } else {
+            self.link.callback(Msg::SwitchedCellWithIndex)
+        };
+        let title: String = if !is_alive {
+            trf("board.field_eliminated", &[name_player.as_str()])
+        } else {
+            let num_ships: usize = self.game.get_player(idx_player).map_or(0, |player| player.get_num_living_ships());
+            format!("{}, {}", trf("board.field", &[name_player.as_str()]), tr_count("board.ships_left", num_ships))
+        };
+        let class_title = if is_own_field && !self.game.is_over() {
+            classes!("title", "active-turn")
+        } else {
+            classes!("title")
+        };
+        let class_eliminated: Option<&str> = if is_alive { None } else { Some("eliminated") };
+        // On narrow screens only the field of the selected tab is shown.
+        let class_tab: Option<&str> = if idx_player == self.cursor.0 { None } else { Some("tab-hidden") };
+        let class_board = classes!("board", class_eliminated, class_tab);
+        // The probabilities are calculated from what the viewer knows about the opponent's field.
+        let heatmap: Option<Vec<f64>> = if self.is_heatmap_shown && is_fog_of_war && is_alive && !self.game.is_over() {
+            self.game.get_player(idx_player).map(|player| player.get_probability_map())
+        } else {
+            None
+        };
+        // The viewer marks the cells of the opponents' fields that he cannot see.
+        let annotate_call: Option<Callback<(usize, usize)>> = if is_fog_of_war && is_alive {
+            Some(self.link.callback(Msg::AnnotatedCellWithIndex))
+        } else {
+            None
+        };
+        let maneuver_controls: Html = if is_moving_fleet {
+            self.view_maneuver_controls(idx_player)
+        } else { html! {} };
+        html! {
+            <div class=class_board>
+                <h1 class=class_title>{ title.clone() }</h1>
+                <div class="field-scroll">
+                <PlayField
+                    caption=title
+                    field_number=idx_player
+                    width_field=self.props.field_setting.width_field
+                    height_field=self.props.field_setting.height_field
+                    is_fog_of_war=is_fog_of_war
+                    is_interactive=is_interactive
+                    parent_call=parent_call
+                    heatmap=heatmap
+                    labels=self.get_shot_numbers(idx_player)
+                    highlighted_cell=self.get_highlighted_cell(idx_player)
+                    cursor_cell=self.get_cursor_cell(idx_player)
+                    sunk_cells=self.get_sunk_cells(idx_player)
+                    annotations=self.get_annotations(idx_player)
+                    annotate_call=annotate_call
+                />
+                </div>
+                { maneuver_controls }
+                <FleetStatus ships=self.get_fleet_status(idx_player) is_fog_of_war=is_fog_of_war />
+            </div>
+        }
+    }
+
+    /// Renders the window with the winners and the choice between a rematch and the review of the game.
+    fn view_game_over(&self) -> Html {
+        let names_winners: &String = match (&self.winners, self.is_game_over_shown) {
+            (Some(names_winners), true) => { names_winners }
+            _ => { return html! {}; }
+        };
+        html! {
+            <Modal title=trf("board.won", &[names_winners.as_str()])>
+                <div class="modal-buttons">
+                    <button onclick=self.link.callback(|_| Msg::Rematch)>{ tr("board.rematch") }</button>
+                    <button onclick=self.link.callback(|_| Msg::GameOverClosed)>{ tr("board.review") }</button>
+                </div>
+            </Modal>
+        }
+    }
+
+    /// Renders the buttons that switch between the fields on narrow screens,
+    /// and the zoom of large fields.
+    fn view_field_tabs(&self) -> Html {
+        let is_large: bool = self.props.field_setting.width_field > LARGE_FIELD_SIZE
+            || self.props.field_setting.height_field > LARGE_FIELD_SIZE;
+        let zoom_controls: Html = if is_large {
+            let is_min_zoom: bool = self.zoom <= MIN_ZOOM;
+            let is_max_zoom: bool = self.zoom >= MAX_ZOOM;
+            html! {
+                <span class="zoom-controls">
+                    <button aria-label=tr("board.zoom_out") disabled=is_min_zoom
+                        onclick=self.link.callback(|_| Msg::Zoomed(false))>{ "\u{2212}" }</button>
+                    { format!("{}%", self.zoom) }
+                    <button aria-label=tr("board.zoom_in") disabled=is_max_zoom
+                        onclick=self.link.callback(|_| Msg::Zoomed(true))>{ "+" }</button>
+                </span>
+            }
+        } else { html! {} };
+        html! {
+            <div class="field-controls">
+                <div class="field-tabs" role="tablist">
+                    { for (0..self.game.get_num_players()).map(|idx_player| {
+                        let is_selected: bool = idx_player == self.cursor.0;
+                        html! {
+                            <button role="tab" aria-selected=is_selected.to_string()
+                                class=if is_selected { "selected" } else { "" }
+                                onclick=self.link.callback(move |_| Msg::FieldTabSelected(idx_player))>
+                                { self.get_player_name(idx_player) }
+                            </button>
+                        }
+                    }) }
+                </div>
+                { zoom_controls }
+            </div>
+        }
+    }
+
+    /// Renders the options of the board for the human players.
+    fn view_board_options(&self) -> Html {
+        if self.viewer.is_none() {
+            return html! {};
+        }
+        html! {
+            <div class="board-options">
+                <label>
+                    <input
+                        type="checkbox"
+                        checked=self.is_heatmap_shown
+                        onclick=self.link.callback(|_| Msg::HeatmapToggled)
+                    />
+                    { tr("board.heatmap") }
+                </label>
+                <span class="keyboard-help">
+                    { tr("board.keys") }
+                    { if self.typed_text.is_empty() { String::new() } else { format!(" [{}]", self.typed_text) } }
+                </span>
+                <span class="keyboard-help">{ tr("board.annotate") }</span>
+                <label>
+                    { tr("board.shoot_at") }
+                    <input
+                        type="text"
+                        size="4"
+                        placeholder="C5"
+                        disabled=self.game.is_over()
+                        onchange=self.link.callback(Msg::CoordinateEntered)
+                    />
+                </label>
+            </div>
+        }
+    }
+
+    /// Returns the order numbers of the shots at the field of the player after the game,
+    /// or nothing while the game is going on.
+    fn get_shot_numbers(&self, idx_player: usize) -> Vec<String> {
+        if !self.game.is_over() {
+            return Vec::new();
+        }
+        let field_setting: &settings::FieldSettings = &self.props.field_setting;
+        let mut labels: Vec<String> = vec![String::new(); field_setting.width_field * field_setting.height_field];
+        let num_shots: usize = self.review_shot.map_or(self.game.get_shots().len(), |idx| idx + 1);
+        for (number, shot) in self.game.get_shots().iter().take(num_shots).enumerate() {
+            if shot.idx_target == idx_player {
+                labels[shot.idx_cell] = (number + 1).to_string();
+            }
+        }
+        labels
+    }
+
+    /// Returns the cell of the shot pointed at in the history if it was made at the field of the player.
+    fn get_highlighted_cell(&self, idx_player: usize) -> Option<usize> {
+        self.hovered_shot
+            .and_then(|idx_shot| self.game.get_shots().get(idx_shot))
+            .filter(|shot| shot.idx_target == idx_player)
+            .map(|shot| shot.idx_cell)
+    }
+
+    /// Returns for each cell of the field of the player the mark put on it by the opponent.
+    fn get_annotations(&self, idx_player: usize) -> Vec<Option<Annotation>> {
+        let size_field: usize = self.props.field_setting.width_field * self.props.field_setting.height_field;
+        (0..size_field).map(|idx_cell| self.game.get_annotation(idx_player, idx_cell)).collect()
+    }
+
+    /// Returns the size and the damage of every ship of the player.
+    fn get_fleet_status(&self, idx_player: usize) -> Vec<ShipStatus> {
+        self.game.get_player(idx_player).map_or(Vec::new(), |player| {
+            player.get_ships_as_iter()
+                .map(|ship| ShipStatus {
+                    name: ship.get_kind().get_name(),
+                    size: ship.get_size(),
+                    num_hits: ship.get_fire_cell_idxes().len(),
+                    is_sunk: !ship.check_is_alive(),
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the cell with the keyboard cursor if it is on the field of the player
+    /// and the board has the focus.
+    fn get_cursor_cell(&self, idx_player: usize) -> Option<usize> {
+        let (idx_field, idx_cell) = self.cursor;
+        if self.is_focused && idx_field == idx_player && !self.game.is_over() {
+            Some(idx_cell)
+        } else {
+            None
+        }
+    }
+
+    /// Returns for each cell of the field of the player "True" if it is a deck of a ship
+    /// sunk by the shown shots.
+    fn get_sunk_cells(&self, idx_player: usize) -> Vec<bool> {
+        let field_setting: &settings::FieldSettings = &self.props.field_setting;
+        let mut sunk_cells: Vec<bool> = vec![false; field_setting.width_field * field_setting.height_field];
+        let num_shots: usize = self.review_shot.map_or(self.game.get_shots().len(), |idx| idx + 1);
+        let player = match self.game.get_player(idx_player) {
+            None => { return sunk_cells; }
+            Some(player) => { player }
+        };
+        for shot in self.game.get_shots().iter().take(num_shots) {
+            if let (true, ShotResult::Sunk(idx_ship)) = (shot.idx_target == idx_player, shot.result) {
+                let area_ship: Vec<usize> = player.get_ships_as_iter().nth(idx_ship)
+                    .map_or(Vec::new(), |ship| ship.get_area_ship());
+                for idx_cell in area_ship {
+                    if let Some(is_sunk) = sunk_cells.get_mut(idx_cell) {
+                        *is_sunk = true;
+                    }
+                }
+            }
+        }
+        sunk_cells
+    }
+
+    /// Returns the result of the shot, for example "sunk (3-decker)".
+    fn get_shot_result_text(&self, shot: &ShotRecord) -> String {
+        match shot.result {
+            ShotResult::Miss => { tr("shot.miss") }
+            ShotResult::Hit => { tr("shot.hit") }
+            ShotResult::Sunk(idx_ship) => {
+                trf("shot.sunk", &[self.get_ship_size(shot.idx_target, idx_ship).to_string().as_str()])
+            }
+        }
+    }
+
+    /// Returns the text about the last shot and whose turn it is for the screen readers.
+    fn get_announcement(&self) -> String {
+        let width_field: usize = self.props.field_setting.width_field;
+        let text_shot: String = match self.game.get_shots().last() {
+            None => { String::new() }
+            Some(shot) => {
+                trf("board.shot", &[
+                    self.get_player_name(shot.idx_player).as_str(),
+                    get_cell_name(shot.idx_cell, width_field).as_str(),
+                    self.get_player_name(shot.idx_target).as_str(),
+                    self.get_shot_result_text(shot).as_str(),
+                ])
+            }
+        };
+        let text_turn: String = match &self.winners {
+            Some(names_winners) => { trf("board.won", &[names_winners.as_str()]) }
+            None => { trf("board.turn", &[self.get_player_name(self.game.get_whose_move()).as_str()]) }
+        };
+        format!("{} {}", text_shot, text_turn)
+    }
+
+    /// Returns the states of the cells of every field after the first shots, as the names
+    /// accepted by the cells. The ships are shown where they are at the end of the game.
+    fn get_cell_states(&self, num_shots: usize) -> Vec<Vec<&'static str>> {
+        let size_field: usize = self.props.field_setting.width_field * self.props.field_setting.height_field;
+        let mut states: Vec<Vec<&'static str>> = self.game.get_players_as_iter()
+            .map(|player| (0..size_field)
+                .map(|idx_cell| if player.get_ship_idx_by_cell(idx_cell).is_some() { "Deck" } else { "Blank" })
+                .collect())
+            .collect();
+        for shot in self.game.get_shots().iter().take(num_shots) {
+            let field: &mut Vec<&'static str> = &mut states[shot.idx_target];
+            match shot.result {
+                ShotResult::Miss => { field[shot.idx_cell] = "Miss"; }
+                ShotResult::Hit => { field[shot.idx_cell] = "Fire"; }
+                ShotResult::Sunk(idx_ship) => {
+                    let (area_ship, area_near_ship) = self.game.get_player(shot.idx_target)
+                        .and_then(|target| target.get_ships_as_iter().nth(idx_ship))
+                        .map_or((Vec::new(), Vec::new()), |ship| (ship.get_area_ship(), ship.get_area_near_ship(None)));
+                    for idx_cell in area_ship {
+                        if let Some(state) = field.get_mut(idx_cell) {
+                            *state = "Sunk";
+                        }
+                    }
+                    // The misses near the ship stay as they were.
+                    for idx_cell in area_near_ship {
+                        if let Some(state) = field.get_mut(idx_cell) {
+                            if *state == "Blank" {
+                                *state = "Water";
+                            }
+                        }
+                    }
+                }
+            }
         }
+        states
     }
-}
\ No newline at end of file
+
+    /// Renders the list of the shots of the game.
+    fn view_history(&self) -> Html {
+        let width_field: usize = self.props.field_setting.width_field;
+        let shots: Vec<HistoryShot> = self.game.get_shots().iter()
+            .map(|shot| HistoryShot {
+                idx_player: shot.idx_player,
+                name_player: self.get_player_name(shot.idx_player),
+                name_target: self.get_player_name(shot.idx_target),
+                cell: get_cell_name(shot.idx_cell, width_field),
+                result: self.get_shot_result_text(shot),
+            })
+            .collect();
+        html! {
+            <HistoryPanel shots=shots review_shot=self.review_shot is_over=self.game.is_over()
+                is_moving_fleet=self.props.rules_setting.is_moving_fleet
+                hover_call=self.link.callback(Msg::HistoryHovered)
+                jump_call=self.link.callback(Msg::HistoryJumped) />
+        }
+    }
+
+    /// Renders the statistics of the finished game.
+    fn view_analysis(&self) -> Html {
+        let num_players: usize = self.game.get_num_players();
+        let stats: Vec<analysis::PlayerStats> = (0..num_players)
+            .map(|idx_player| analysis::get_player_stats(&self.game, idx_player))
+            .collect();
+        let humans: Vec<usize> = (0..num_players)
+            .filter(|&idx_player| self.is_manual_control(idx_player))
+            .collect();
+        html! {
+            <AnalysisView winners=self.winners.clone() names_players=self.get_player_names() stats=stats
+                humans=humans ratings=self.shot_ratings.clone() width_field=self.props.field_setting.width_field />
+        }
+    }
+
+    /// Renders the buttons for the maneuver of the selected ship.
+    fn view_maneuver_controls(&self, idx_player: usize) -> Html {
+        let text_selected: String = match self.selected_ship {
+            None => { tr("board.select_ship") }
+            Some(idx_ship) => {
+                let name_kind = self.game.get_player(idx_player)
+                    .and_then(|player| player.get_ships_as_iter().nth(idx_ship))
+                    .map_or(String::new(), |ship| ship.get_kind().get_name());
+                trf("board.selected_ship", &[name_kind.as_str()])
+            }
+        };
+        let is_disabled = self.selected_ship.is_none();
+        html! {
+            <div class="maneuver-controls">
+                <p>{ text_selected }</p>
+                <button disabled=is_disabled
+                    onclick=self.link.callback(|_| Msg::ManeuveredShip(Maneuver::Left))>{ "\u{2190}" }</button>
+                <button disabled=is_disabled
+                    onclick=self.link.callback(|_| Msg::ManeuveredShip(Maneuver::Up))>{ "\u{2191}" }</button>
+                <button disabled=is_disabled
+                    onclick=self.link.callback(|_| Msg::ManeuveredShip(Maneuver::Down))>{ "\u{2193}" }</button>
+                <button disabled=is_disabled
+                    onclick=self.link.callback(|_| Msg::ManeuveredShip(Maneuver::Right))>{ "\u{2192}" }</button>
+                <button disabled=is_disabled
+                    onclick=self.link.callback(|_| Msg::ManeuveredShip(Maneuver::Rotate))>{ "\u{27f3}" }</button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use yew::{ComponentLink, Component, Html, html, classes, ChangeData};
+use crate::settings;
+use crate::components::cell::Coordinates;
+use crate::objects::puzzle::{Puzzle, Mark, Difficulty, Hint, HintReason};
+use crate::objects::shape::get_fleet_description;
+use crate::i18n::{tr, trf};
+
+pub enum Msg {
+    // CellClicked: Changes the mark of the cell: unknown, water, ship segment.
+    CellClicked(usize),
+    DifficultyChange(ChangeData),
+    HintRequested,
+    CheckRequested,
+    NewPuzzle,
+}
+
+/// Battleship solitaire: the player finds the hidden fleet
+/// from the numbers of decks in rows and columns and the revealed cells.
+pub struct PuzzleBoard {
+    link: ComponentLink<Self>,
+    props: settings::SettingsProps,
+    puzzle: Puzzle,
+    // difficulty: The difficulty requested for the next puzzle.
+    difficulty: Difficulty,
+    marks: Vec<Mark>,
+    // highlighted_cells: The cells pointed out by the last hint or check.
+    highlighted_cells: Vec<usize>,
+    // message: The result of the last hint or check.
+    message: String,
+}
+
+impl PuzzleBoard {
+    fn generate_puzzle(props: &settings::SettingsProps, difficulty: Difficulty) -> Puzzle {
+        Puzzle::generate(
+            props.field_setting.width_field,
+            props.field_setting.height_field,
+            &props.field_setting.fleet,
+            difficulty,
+        )
+    }
+
+    fn start_new_puzzle(&mut self) {
+        self.puzzle = PuzzleBoard::generate_puzzle(&self.props, self.difficulty);
+        self.marks = self.puzzle.get_initial_marks();
+        self.highlighted_cells.clear();
+        self.message.clear();
+    }
+
+    fn is_solved(&self) -> bool {
+        self.puzzle.is_solved(&self.marks)
+    }
+
+    /// Returns the number of cells marked as ship segments among the given cells.
+    fn get_num_marked_ships(&self, cells: impl Iterator<Item=usize>) -> usize {
+        cells.filter(|&idx| self.marks[idx] == Mark::Ship).count()
+    }
+}
+
+impl Component for PuzzleBoard {
+    type Message = Msg;
+    type Properties = settings::SettingsProps;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let difficulty = Difficulty::Medium;
+        let puzzle = PuzzleBoard::generate_puzzle(&props, difficulty);
+        let marks = puzzle.get_initial_marks();
+        Self {
+            link,
+            props,
+            puzzle,
+            difficulty,
+            marks,
+            highlighted_cells: Vec::new(),
+            message: String::new(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> bool {
+        match msg {
+            Msg::CellClicked(idx_cell) => {
+                if self.is_solved() || self.puzzle.is_revealed(idx_cell) {
+                    return false;
+                }
+                self.marks[idx_cell] = match self.marks[idx_cell] {
+                    Mark::Unknown => Mark::Water,
+                    Mark::Water => Mark::Ship,
+                    Mark::Ship => Mark::Unknown,
+                };
+                self.highlighted_cells.clear();
+                self.message = if self.is_solved() {
+                    tr("puzzle.solved")
+                } else {
+                    String::new()
+                };
+                true
+            }
+            Msg::DifficultyChange(e) => {
+                if let ChangeData::Select(el) = e {
+                    self.difficulty = match el.value().as_str() {
+                        "easy" => Difficulty::Easy,
+                        "hard" => Difficulty::Hard,
+                        _ => Difficulty::Medium,
+                    };
+                }
+                false
+            }
+            Msg::HintRequested => {
+                if let Some(hint) = self.puzzle.get_hint(&self.marks) {
+                    self.marks[hint.idx_cell] = hint.mark;
+                    self.highlighted_cells = vec![hint.idx_cell];
+                    self.message = get_hint_text(&hint, self.puzzle.get_width_field());
+                }
+                true
+            }
+            Msg::CheckRequested => {
+                let mistakes = self.puzzle.get_mistakes(&self.marks);
+                self.message = if self.is_solved() {
+                    tr("puzzle.solved")
+                } else if mistakes.is_empty() {
+                    tr("puzzle.no_mistakes")
+                } else {
+                    trf("puzzle.mistakes", &[mistakes.len().to_string().as_str()])
+                };
+                self.highlighted_cells = mistakes;
+                true
+            }
+            Msg::NewPuzzle => {
+                self.start_new_puzzle();
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> bool {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let width_field = self.puzzle.get_width_field();
+        let height_field = self.puzzle.get_height_field();
+        let column_counts = self.puzzle.get_column_counts();
+        let row_counts = self.puzzle.get_row_counts();
+        html! {
+            <div class="wrapper">
+                <div class="board">
+                    <h1 class="title">{ tr("settings.mode_puzzle") }</h1>
+                    <p class="solo-status">
+                        { trf(
+                            "puzzle.status",
+                            &[
+                                self.puzzle.get_difficulty().get_name().as_str(),
+                                get_fleet_description(self.puzzle.get_fleet()).as_str(),
+                            ],
+                        ) }
+                    </p>
+                    <div class="puzzle">
+                        { for (0..height_field).map(|y| html! {
+                            <div key=y class="game-row">
+                                { for (0..width_field).map(|x| self.view_cell(y * width_field + x)) }
+                                { view_count(
+                                    row_counts[y],
+                                    self.get_num_marked_ships((0..width_field).map(|x| y * width_field + x)),
+                                ) }
+                            </div>
+                        }) }
+                        <div class="game-row">
+                            { for (0..width_field).map(|x| view_count(
+                                column_counts[x],
+                                self.get_num_marked_ships((0..height_field).map(|y| y * width_field + x)),
+                            )) }
+                        </div>
+                    </div>
+                    <p class="puzzle-message">{ self.message.clone() }</p>
+                    <div class="puzzle-controls">
+                        <button onclick=self.link.callback(|_| Msg::HintRequested) disabled=self.is_solved()>
+                            { tr("puzzle.hint") }
+                        </button>
+                        <button onclick=self.link.callback(|_| Msg::CheckRequested)>{ tr("puzzle.check") }</button>
+                        <select onchange=self.link.callback(Msg::DifficultyChange)>
+                            <option value="easy" selected={ self.difficulty == Difficulty::Easy }>{ Difficulty::Easy.get_name() }</option>
+                            <option value="medium" selected={ self.difficulty == Difficulty::Medium }>{ Difficulty::Medium.get_name() }</option>
+                            <option value="hard" selected={ self.difficulty == Difficulty::Hard }>{ Difficulty::Hard.get_name() }</option>
+                        </select>
+                        <button onclick=self.link.callback(|_| Msg::NewPuzzle)>{ tr("puzzle.new") }</button>
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
+
+impl PuzzleBoard {
+    /// Renders a cell with the mark of the player.
+    fn view_cell(&self, idx_cell: usize) -> Html {
+        let cell_status: &str = match self.marks[idx_cell] {
+            Mark::Unknown => "cell-blank",
+            Mark::Water => "cell-miss",
+            Mark::Ship => "cell-deck",
+        };
+        let class_revealed: Option<&str> = if self.puzzle.is_revealed(idx_cell) { Some("revealed") } else { None };
+        let class_highlighted: Option<&str> = if self.highlighted_cells.contains(&idx_cell) {
+            Some("highlighted")
+        } else {
+            None
+        };
+        html! {
+            <div key=idx_cell class=classes!("game-cells", class_revealed, class_highlighted)
+                onclick=self.link.callback(move |_| Msg::CellClicked(idx_cell))>
+                <div class=classes!(cell_status)></div>
+            </div>
+        }
+    }
+}
+
+/// Renders the number of decks of a row or column,
+/// which is marked when the player has found that many ship segments.
+fn view_count(count: usize, num_marked: usize) -> Html {
+    let class_done: Option<&str> = if num_marked == count { Some("done") } else { None };
+    let class_over: Option<&str> = if num_marked > count { Some("over") } else { None };
+    html! {
+        <div class=classes!("game-cells", "puzzle-count", class_done, class_over)>{ count }</div>
+    }
+}
+
+/// Returns the explanation of the hint.
+fn get_hint_text(hint: &Hint, width_field: usize) -> String {
+    let key_mark: &str = match hint.mark {
+        Mark::Ship => "puzzle.hint_ship",
+        _ => "puzzle.hint_water",
+    };
+    let key_reason: &str = match hint.reason {
+        HintReason::Mistake => "puzzle.reason_mistake",
+        HintReason::LineFull => "puzzle.reason_line_full",
+        HintReason::LineNeedsAll => "puzzle.reason_line_needs_all",
+        HintReason::NearShip => "puzzle.reason_near_ship",
+        HintReason::NoShipFits => "puzzle.reason_no_ship_fits",
+        HintReason::Solution => "puzzle.reason_solution",
+    };
+    trf(
+        key_mark,
+        &[
+            Coordinates::from_index(hint.idx_cell, width_field).to_a1().as_str(),
+            tr(key_reason).as_str(),
+        ],
+    )
+}
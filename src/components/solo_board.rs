@@ -0,0 +1,548 @@
+use yew::{ComponentLink, Component, Html, html, Callback, Dispatched};
+use yew::agent::Dispatcher;
+use yew::services::{IntervalService, TimeoutService};
+use yew::services::interval::IntervalTask;
+use yew::services::timeout::TimeoutTask;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use rand::Rng;
+use crate::components::field::PlayField;
+use crate::agents::agent_w;
+use crate::settings;
+use crate::storage;
+use crate::objects::player;
+use crate::objects::daily;
+use crate::objects::shape::{get_fleet_description, get_fleet_id};
+use crate::i18n::{tr, trf};
+
+// KEY_BEST_SCORES: The key of the best results in the local storage.
+const KEY_BEST_SCORES: &str = "sea_battle.solo.best_scores";
+// KEY_DAILY_RESULTS: The key of the results of the daily challenges in the local storage.
+const KEY_DAILY_RESULTS: &str = "sea_battle.daily.results";
+// NUM_BEST_SCORES: How many best results are kept for each field size and fleet.
+const NUM_BEST_SCORES: usize = 5;
+// IDX_FIELD: The index of the only field on the board.
+const IDX_FIELD: usize = 0;
+// KEY_PARS: The key of the calculated pars in the local storage.
+const KEY_PARS: &str = "sea_battle.solo.pars";
+// NUM_CACHED_PARS: How many last calculated pars are kept.
+const NUM_CACHED_PARS: usize = 20;
+// NUM_PAR_SHOTS_PER_STEP: How many shots of the computer are simulated between redraws of the page.
+const NUM_PAR_SHOTS_PER_STEP: usize = 5;
+
+/// The result of a solo game.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SoloScore {
+    // board: The size of the field, for example "10x10".
+    pub board: String,
+    // fleet: The description of the fleet that does not depend on the language.
+    pub fleet: String,
+    pub shots: usize,
+    pub par: usize,
+    pub seconds: u64,
+}
+
+/// The par calculated for a layout.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct CachedPar {
+    board: String,
+    fleet: String,
+    seed: u64,
+    par: usize,
+}
+
+pub enum Msg {
+    SwitchedCellWithIndex((usize, usize)),
+    // Tick: One second has passed.
+    Tick,
+    // Simulating the next shots of the computer for the par.
+    ParStep,
+}
+
+/// A single player game: sink a generated fleet in as few shots and as little time as possible.
+/// In the daily challenge, the fleet is the same for everyone on the same date.
+pub struct SoloBoard {
+    link: ComponentLink<Self>,
+    props: settings::SettingsProps,
+    // field_setting: The field of the game, in the daily challenge it does not depend on the settings.
+    field_setting: settings::FieldSettings,
+    // daily_date: The date of the daily challenge, or None for a usual solo game.
+    daily_date: Option<String>,
+    target: player::Player,
+    // seed: The seed of the layout of the fleet.
+    seed: u64,
+    // par: The number of shots the computer needs for the same layout, None while it is calculated.
+    par: Option<usize>,
+    par_calculation: Option<player::ParCalculation>,
+    // par_task: The timer of the next step of the calculation of the par.
+    par_task: Option<TimeoutTask>,
+    num_shots: usize,
+    // shots_pattern: For each shot in order, "True" if it hit a deck.
+    shots_pattern: Vec<bool>,
+    seconds: u64,
+    // The timer is started by the first shot and stopped when the fleet is sunk.
+    timer: Option<IntervalTask>,
+    best_scores: Vec<SoloScore>,
+    daily_results: Vec<daily::DailyResult>,
+    // is_daily_played: "True" if the result of today's challenge was already saved before this game.
+    is_daily_played: bool,
+    event_work: Dispatcher<agent_w::Worker>,
+}
+
+impl SoloBoard {
+    /// Returns the size of the field as it is shown in the table of results.
+    fn get_board_description(&self) -> String {
+        format!("{}x{}", self.field_setting.width_field, self.field_setting.height_field)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.target.get_num_living_ships() == 0
+    }
+
+    /// Returns the par calculated earlier for the same layout.
+    fn find_cached_par(&self) -> Option<usize> {
+        let board: String = self.get_board_description();
+        let fleet: String = get_fleet_id(&self.field_setting.fleet);
+        storage::load::<Vec<CachedPar>>(KEY_PARS).iter()
+            .find(|cached| cached.board == board && cached.fleet == fleet && cached.seed == self.seed)
+            .map(|cached| cached.par)
+    }
+
+    /// Remembers the par of the layout, only the last ones are kept.
+    fn cache_par(&self, par: usize) {
+        let mut cached_pars: Vec<CachedPar> = storage::load(KEY_PARS);
+        cached_pars.push(CachedPar {
+            board: self.get_board_description(),
+            fleet: get_fleet_id(&self.field_setting.fleet),
+            seed: self.seed,
+            par,
+        });
+        if cached_pars.len() > NUM_CACHED_PARS {
+            cached_pars.drain(..cached_pars.len() - NUM_CACHED_PARS);
+        }
+        storage::save(KEY_PARS, &cached_pars);
+    }
+
+    /// Simulates the next shots of the computer, the calculation goes on after the page is redrawn.
+    /// If "is_completed", then the calculation is finished at once.
+    fn advance_par(&mut self, is_completed: bool) {
+        let calculation: &mut player::ParCalculation = match self.par_calculation.as_mut() {
+            None => { return; }
+            Some(calculation) => { calculation }
+        };
+        let max_shots: usize = if is_completed { usize::MAX } else { NUM_PAR_SHOTS_PER_STEP };
+        match calculation.advance(max_shots) {
+            None => {
+                self.par_task = Some(TimeoutService::spawn(
+                    Duration::from_millis(0),
+                    self.link.callback(|_| Msg::ParStep),
+                ));
+            }
+            Some(par) => {
+                self.par = Some(par);
+                self.par_calculation = None;
+                self.par_task = None;
+                self.cache_par(par);
+            }
+        }
+    }
+
+    /// Saves the result of the game among the best results.
+    fn save_score(&mut self) {
+        let score = SoloScore {
+            board: self.get_board_description(),
+            fleet: get_fleet_id(&self.field_setting.fleet),
+            shots: self.num_shots,
+            par: self.par.unwrap_or(0),
+            seconds: self.seconds,
+        };
+        let mut best_scores: Vec<SoloScore> = storage::load(KEY_BEST_SCORES);
+        best_scores.push(score.clone());
+        // For each field and fleet, the best results by shots and by time are kept.
+        let mut same_scores: Vec<SoloScore> = best_scores.iter()
+            .filter(|other| other.board == score.board && other.fleet == score.fleet)
+            .cloned()
+            .collect();
+        best_scores.retain(|other| other.board != score.board || other.fleet != score.fleet);
+        same_scores.sort_by_key(|other| (other.shots, other.seconds));
+        let mut kept_scores: Vec<SoloScore> = same_scores.iter().take(NUM_BEST_SCORES).cloned().collect();
+        same_scores.sort_by_key(|other| (other.seconds, other.shots));
+        for other in same_scores.iter().take(NUM_BEST_SCORES) {
+            if !kept_scores.contains(other) {
+                kept_scores.push(other.clone());
+            }
+        }
+        best_scores.extend(kept_scores);
+        storage::save(KEY_BEST_SCORES, &best_scores);
+        self.best_scores = best_scores;
+    }
+
+    /// Saves the result of the daily challenge, only the first game of the day counts.
+    fn save_daily_result(&mut self) {
+        let date: String = match &self.daily_date {
+            None => { return; }
+            Some(date) => { date.clone() }
+        };
+        let mut daily_results: Vec<daily::DailyResult> = storage::load(KEY_DAILY_RESULTS);
+        if daily_results.iter().any(|result| result.date == date) {
+            return;
+        }
+        daily_results.push(daily::DailyResult {
+            date,
+            shots: self.num_shots,
+            par: self.par.unwrap_or(0),
+            seconds: self.seconds,
+            shots_pattern: self.shots_pattern.clone(),
+        });
+        storage::save(KEY_DAILY_RESULTS, &daily_results);
+        self.daily_results = daily_results;
+    }
+}
+
+impl Component for SoloBoard {
+    type Message = Msg;
+    type Properties = settings::SettingsProps;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let daily_date: Option<String> = if props.rules_setting.mode == settings::GameMode::Daily {
+            Some(daily::get_today())
+        } else {
+            None
+        };
+        let (field_setting, seed): (settings::FieldSettings, u64) = match &daily_date {
+            None => { (props.field_setting.clone(), rand::thread_rng().gen::<u64>()) }
+            Some(date) => {
+                let field_setting = settings::FieldSettings {
+                    width_field: daily::WIDTH_FIELD,
+                    height_field: daily::HEIGHT_FIELD,
+                    fleet: daily::get_daily_fleet(),
+                };
+                (field_setting, daily::get_daily_seed(date))
+            }
+        };
+        let daily_results: Vec<daily::DailyResult> = storage::load(KEY_DAILY_RESULTS);
+        let is_daily_played: bool = daily_results.iter()
+            .any(|result| Some(&result.date) == daily_date.as_ref());
+        let target = player::Player::create(
+            "Computer",
+            false,
+            field_setting.width_field,
+            field_setting.height_field,
+            &field_setting.fleet,
+            Some(seed),
+        );
+        let par_calculation = player::ParCalculation::create(
+            field_setting.width_field,
+            field_setting.height_field,
+            &field_setting.fleet,
+            seed,
+        );
+        let event_work = agent_w::Worker::dispatcher();
+        let mut solo_board = Self {
+            link,
+            props,
+            field_setting,
+            daily_date,
+            target,
+            seed,
+            par: None,
+            par_calculation: Some(par_calculation),
+            par_task: None,
+            num_shots: 0,
+            shots_pattern: Vec::new(),
+            seconds: 0,
+            timer: None,
+            best_scores: storage::load(KEY_BEST_SCORES),
+            daily_results,
+            is_daily_played,
+            event_work,
+        };
+        match solo_board.find_cached_par() {
+            Some(par) => {
+                solo_board.par = Some(par);
+                solo_board.par_calculation = None;
+            }
+            None => { solo_board.advance_par(false); }
+        }
+        solo_board
+    }
+
+    fn update(&mut self, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ParStep => {
+                self.advance_par(false);
+                self.par.is_some()
+            }
+            Msg::SwitchedCellWithIndex((idx_cell, _)) => {
+                if self.is_finished() || self.target.is_cell_shot(idx_cell) {
+                    return false;
+                }
+                if self.timer.is_none() {
+                    self.timer = Some(IntervalService::spawn(
+                        Duration::from_secs(1),
+                        self.link.callback(|_| Msg::Tick),
+                    ));
+                }
+                self.num_shots += 1;
+                let (number_ship, is_successful_shot, is_alive_ship) = self.target.process_a_shot(idx_cell);
+                self.shots_pattern.push(is_successful_shot);
+                // If the shot turned out to be fatal, then the whole ship is shown as sunk
+                // and the adjacent cells that were not shot as water.
+                let sunk_ship = number_ship.filter(|_| is_successful_shot && !is_alive_ship)
+                    .and_then(|idx_ship| self.target.get_ship_by_idx_as_mut_ref(idx_ship))
+                    .map(|ship| (ship.get_area_ship(), ship.get_area_near_ship(None)));
+                match sunk_ship {
+                    Some((area_ship, area_near_ship)) => {
+                        for idx in area_ship {
+                            self.event_work.send(agent_w::Request::GetStateCell(idx, IDX_FIELD, "Sunk".to_string()))
+                        }
+                        for idx in area_near_ship {
+                            if !self.target.is_cell_shot(idx) {
+                                self.event_work.send(agent_w::Request::GetStateCell(idx, IDX_FIELD, "Water".to_string()))
+                            }
+                        }
+                    }
+                    None => {
+                        self.event_work.send(agent_w::Request::Shot(idx_cell, IDX_FIELD));
+                    }
+                }
+                if self.is_finished() {
+                    self.timer = None;
+                    // The result is compared with the par, so it must be known by now.
+                    self.advance_par(true);
+                    if self.daily_date.is_some() {
+                        self.save_daily_result();
+                    } else {
+                        self.save_score();
+                    }
+                }
+                true
+            }
+            Msg::Tick => {
+                self.seconds += 1;
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> bool {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let parent_call: Callback<(usize, usize)> = self.link.callback(Msg::SwitchedCellWithIndex);
+        let text_par: String = self.par.map_or("...".to_string(), |par| par.to_string());
+        let text_status = trf(
+            "solo.status",
+            &[self.num_shots.to_string().as_str(), text_par.as_str(), format_seconds(self.seconds).as_str()],
+        );
+        let result: Html = if self.is_finished() {
+            html! {
+                <>
+                <p class="solo-result">
+                    { trf(
+                        "solo.result",
+                        &[
+                            self.num_shots.to_string().as_str(),
+                            format_relative_to_par(self.num_shots, self.par.unwrap_or(0)).as_str(),
+                            format_seconds(self.seconds).as_str(),
+                        ],
+                    ) }
+                </p>
+                {
+                    if self.daily_date.is_some() {
+                        self.view_daily_results()
+                    } else {
+                        self.view_best_scores()
+                    }
+                }
+                </>
+            }
+        } else { html! {} };
+        let title: String = match &self.daily_date {
+            None => { tr("solo.title") }
+            Some(date) => { trf("solo.daily_title", &[date.as_str()]) }
+        };
+        let note_practice: Html = if self.is_daily_played {
+            html! {
+                <p class="solo-note">
+                    { tr("solo.practice") }
+                </p>
+            }
+        } else { html! {} };
+        html! {
+            <div class="wrapper">
+                <div class="board">
+                    <h1 class="title">{ title }</h1>
+                    { note_practice }
+                    <p class="solo-status">{ text_status }</p>
+                    <PlayField
+                        field_number=IDX_FIELD
+                        width_field=self.field_setting.width_field
+                        height_field=self.field_setting.height_field
+                        is_fog_of_war=true
+                        is_interactive=!self.is_finished()
+                        parent_call=parent_call
+                        sunk_cells=self.get_sunk_cells()
+                    />
+                    { result }
+                </div>
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            for ship in self.target.get_ships_as_iter() {
+                for &idx_cell in ship.get_area_ship().iter() {
+                    self.event_work.send(
+                        agent_w::Request::GetStateCell(idx_cell, IDX_FIELD, "Deck".to_string())
+                    )
+                }
+            }
+        }
+    }
+}
+
+impl SoloBoard {
+    /// Returns for each cell of the field "True" if it is a deck of a sunk ship.
+    fn get_sunk_cells(&self) -> Vec<bool> {
+        let mut sunk_cells: Vec<bool> = vec![false; self.field_setting.width_field * self.field_setting.height_field];
+        for ship in self.target.get_ships_as_iter().filter(|ship| !ship.check_is_alive()) {
+            for idx_cell in ship.get_area_ship() {
+                if let Some(is_sunk) = sunk_cells.get_mut(idx_cell) {
+                    *is_sunk = true;
+                }
+            }
+        }
+        sunk_cells
+    }
+
+    /// Renders the best results for the current field size and fleet.
+    fn view_best_scores(&self) -> Html {
+        let board = self.get_board_description();
+        let fleet = get_fleet_id(&self.field_setting.fleet);
+        let name_fleet = get_fleet_description(&self.field_setting.fleet);
+        let mut scores: Vec<&SoloScore> = self.best_scores.iter()
+            .filter(|score| score.board == board && score.fleet == fleet)
+            .collect();
+        scores.sort_by_key(|score| (score.shots, score.seconds));
+        let by_shots: Vec<&SoloScore> = scores.iter().take(NUM_BEST_SCORES).cloned().collect();
+        scores.sort_by_key(|score| (score.seconds, score.shots));
+        let by_time: Vec<&SoloScore> = scores.iter().take(NUM_BEST_SCORES).cloned().collect();
+        html! {
+            <div class="best-scores">
+                <h2>{ trf("solo.best_results", &[board.as_str(), name_fleet.as_str()]) }</h2>
+                { view_scores_table(tr("solo.fewest_shots").as_str(), &by_shots) }
+                { view_scores_table(tr("solo.fastest").as_str(), &by_time) }
+            </div>
+        }
+    }
+}
+
+impl SoloBoard {
+    /// Renders the text to share the result of today's challenge and the previous results.
+    fn view_daily_results(&self) -> Html {
+        let result: Option<&daily::DailyResult> = self.daily_results.iter()
+            .find(|result| Some(&result.date) == self.daily_date.as_ref());
+        let share_text: Html = match result {
+            None => { html! {} }
+            Some(result) => {
+                html! {
+                    <>
+                    <h2>{ tr("solo.share") }</h2>
+                    <textarea class="share-text" readonly=true rows="6" cols="24"
+                        value=get_share_text(result)
+                    />
+                    </>
+                }
+            }
+        };
+        let mut results: Vec<&daily::DailyResult> = self.daily_results.iter().collect();
+        results.sort_by(|a, b| b.date.cmp(&a.date));
+        html! {
+            <div class="best-scores">
+                { share_text }
+                <table class="scores">
+                    <caption>{ tr("solo.daily_results") }</caption>
+                    <tr>
+                        <th>{ tr("solo.date") }</th>
+                        <th>{ tr("solo.shots") }</th>
+                        <th>{ tr("solo.par") }</th>
+                        <th>{ tr("solo.time") }</th>
+                    </tr>
+                    { for results.iter().map(|result| html! {
+                        <tr>
+                            <td>{ result.date.clone() }</td>
+                            <td>{ result.shots }</td>
+                            <td>{ format_relative_to_par(result.shots, result.par) }</td>
+                            <td>{ format_seconds(result.seconds) }</td>
+                        </tr>
+                    }) }
+                </table>
+            </div>
+        }
+    }
+}
+
+/// Returns a short text with the result of the daily challenge:
+/// the summary line and the shots in order, a red square for a hit and a blue one for a miss.
+pub fn get_share_text(result: &daily::DailyResult) -> String {
+    let mut lines: Vec<String> = vec![
+        trf("solo.share_title", &[result.date.as_str()]),
+        trf(
+            "solo.share_summary",
+            &[
+                result.shots.to_string().as_str(),
+                format_relative_to_par(result.shots, result.par).as_str(),
+                format_seconds(result.seconds).as_str(),
+            ],
+        ),
+    ];
+    for chunk in result.shots_pattern.chunks(10) {
+        lines.push(chunk.iter()
+            .map(|&is_hit| if is_hit { "\u{1f7e5}" } else { "\u{1f7e6}" })
+            .collect());
+    }
+    lines.join("\n")
+}
+
+/// Renders the table of results.
+fn view_scores_table(caption: &str, scores: &[&SoloScore]) -> Html {
+    html! {
+        <table class="scores">
+            <caption>{ caption }</caption>
+            <tr>
+                <th>{ "#" }</th>
+                <th>{ tr("solo.shots") }</th>
+                <th>{ tr("solo.par") }</th>
+                <th>{ tr("solo.time") }</th>
+            </tr>
+            { for scores.iter().enumerate().map(|(position, score)| html! {
+                <tr>
+                    <td>{ position + 1 }</td>
+                    <td>{ score.shots }</td>
+                    <td>{ format_relative_to_par(score.shots, score.par) }</td>
+                    <td>{ format_seconds(score.seconds) }</td>
+                </tr>
+            }) }
+        </table>
+    }
+}
+
+/// Returns the time in the form "m:ss".
+pub fn format_seconds(seconds: u64) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Returns the number of shots relative to the par, for example "+3", "-1" or "par".
+pub fn format_relative_to_par(shots: usize, par: usize) -> String {
+    if shots == par {
+        tr("solo.even_par")
+    } else if shots > par {
+        format!("+{}", shots - par)
+    } else {
+        format!("-{}", par - shots)
+    }
+}
@@ -0,0 +1,301 @@
+use yew::{ComponentLink, Component, Html, html, ChangeData, InputData};
+use yew::services::TimeoutService;
+use yew::services::timeout::TimeoutTask;
+use std::time::Duration;
+use crate::objects::profile::{self, Profile, Summary};
+use crate::objects::rating::{self, AiRating, Calibration};
+use crate::components::solo_board::format_seconds;
+use crate::i18n::{tr, trf};
+use crate::settings;
+
+// NUM_CHART_GAMES: How many last games are shown on the charts.
+const NUM_CHART_GAMES: usize = 50;
+// CHART_WIDTH, CHART_HEIGHT: The size of the charts in pixels.
+const CHART_WIDTH: f64 = 400.0;
+const CHART_HEIGHT: f64 = 120.0;
+
+pub enum Msg {
+    ProfileSelected(ChangeData),
+    ImportTextChange(InputData),
+    Imported,
+    // Calibrating the ratings of the computer levels anew by self-play.
+    AiRecalibrated,
+    // Playing the next game of the calibration.
+    CalibrationStep,
+}
+
+/// The lifetime statistics of the local profiles with export and import.
+pub struct StatsPage {
+    link: ComponentLink<Self>,
+    profiles: Vec<Profile>,
+    ai_ratings: Vec<AiRating>,
+    // idx_selected: The index of the profile whose statistics are shown.
+    idx_selected: usize,
+    import_text: String,
+    // message: The result of the last import.
+    message: String,
+    // calibration: The calibration of the computer levels in progress.
+    calibration: Option<Calibration>,
+    // calibration_task: The timer of the next game of the calibration.
+    calibration_task: Option<TimeoutTask>,
+}
+
+impl Component for StatsPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            profiles: profile::load_profiles(),
+            ai_ratings: rating::load_ai_ratings(),
+            idx_selected: 0,
+            import_text: String::new(),
+            message: String::new(),
+            calibration: None,
+            calibration_task: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ProfileSelected(e) => {
+                if let ChangeData::Select(el) = e {
+                    self.idx_selected = el.value().parse::<usize>().unwrap_or(0);
+                }
+                true
+            }
+            Msg::ImportTextChange(e) => {
+                self.import_text = e.value;
+                false
+            }
+            Msg::Imported => {
+                self.message = match profile::import_profiles(self.import_text.as_str()) {
+                    None => { tr("stats.import_failed") }
+                    Some(num_profiles) => {
+                        self.import_text.clear();
+                        trf("stats.imported", &[num_profiles.to_string().as_str()])
+                    }
+                };
+                self.profiles = profile::load_profiles();
+                true
+            }
+            Msg::AiRecalibrated => {
+                if self.calibration.is_some() {
+                    return false;
+                }
+                self.calibration = Some(Calibration::create());
+                self.schedule_calibration_step();
+                true
+            }
+            Msg::CalibrationStep => {
+                let calibration: &mut Calibration = match self.calibration.as_mut() {
+                    None => { return false; }
+                    Some(calibration) => { calibration }
+                };
+                calibration.play_next_game();
+                if calibration.is_finished() {
+                    self.ai_ratings = calibration.save();
+                    self.calibration = None;
+                    self.calibration_task = None;
+                } else {
+                    self.schedule_calibration_step();
+                }
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> bool {
+        false
+    }
+
+    fn view(&self) -> Html {
+        let profile_stats: Html = match self.profiles.get(self.idx_selected) {
+            None => { html! { <p>{ tr("stats.no_games") }</p> } }
+            Some(profile) => { self.view_profile(profile) }
+        };
+        html! {
+            <div class="wrapper">
+                <div class="board stats">
+                    <h1 class="title">{ tr("main.statistics") }</h1>
+                    <select onchange=self.link.callback(Msg::ProfileSelected)>
+                        { for self.profiles.iter().enumerate().map(|(idx, profile)| html! {
+                            <option value=idx.to_string() selected={ idx == self.idx_selected }>
+                                { profile.name.clone() }
+                            </option>
+                        }) }
+                    </select>
+                    { profile_stats }
+                    { self.view_leaderboard() }
+                    <h2>{ tr("stats.export") }</h2>
+                    <textarea class="share-text" readonly=true rows="4" cols="60"
+                        value=profile::export_profiles(&self.profiles)
+                    />
+                    <h2>{ tr("stats.import") }</h2>
+                    <textarea class="share-text" rows="4" cols="60"
+                        value=self.import_text.clone()
+                        oninput=self.link.callback(Msg::ImportTextChange)
+                    />
+                    <div>
+                        <button onclick=self.link.callback(|_| Msg::Imported)>{ tr("stats.import") }</button>
+                    </div>
+                    <p>{ self.message.clone() }</p>
+                </div>
+            </div>
+        }
+    }
+}
+
+impl StatsPage {
+    /// Plays the next game of the calibration after the page is redrawn.
+    fn schedule_calibration_step(&mut self) {
+        self.calibration_task = Some(TimeoutService::spawn(
+            Duration::from_millis(0),
+            self.link.callback(|_| Msg::CalibrationStep),
+        ));
+    }
+
+    /// Renders the summary, the table by opponents and rules, and the charts of the profile.
+    fn view_profile(&self, profile: &Profile) -> Html {
+        let summary: Summary = profile.get_summary();
+        let games: Vec<&profile::GameRecord> = profile.games.iter()
+            .skip(profile.games.len().saturating_sub(NUM_CHART_GAMES))
+            .collect();
+        let accuracy: Vec<f64> = games.iter()
+            .map(|game| if game.shots == 0 { 0.0 } else { game.hits as f64 / game.shots as f64 })
+            .collect();
+        // The share of wins among all games up to each game.
+        let num_skipped: usize = profile.games.len() - games.len();
+        let mut num_wins: usize = profile.games.iter().take(num_skipped).filter(|game| game.is_win).count();
+        let win_rate: Vec<f64> = games.iter().enumerate()
+            .map(|(idx, game)| {
+                if game.is_win {
+                    num_wins += 1;
+                }
+                num_wins as f64 / (num_skipped + idx + 1) as f64
+            })
+            .collect();
+        html! {
+            <>
+            <table class="scores">
+                <caption>{ tr("stats.lifetime") }</caption>
+                { view_summary_header("") }
+                { view_summary_row(tr("stats.all_games").as_str(), "", &summary) }
+            </table>
+            <table class="scores">
+                <caption>{ tr("stats.by_opponent") }</caption>
+                { view_summary_header(tr("stats.rules").as_str()) }
+                { for profile.get_summaries_by_opponent_and_rules().iter().map(|(opponent, rules, summary)| {
+                    view_summary_row(
+                        profile::get_opponent_name(*opponent).as_str(),
+                        settings::describe_rules(rules).as_str(),
+                        summary,
+                    )
+                }) }
+            </table>
+            { view_chart(tr("analysis.accuracy").as_str(), &accuracy) }
+            { view_chart(tr("stats.win_rate").as_str(), &win_rate) }
+            </>
+        }
+    }
+}
+
+impl StatsPage {
+    /// Renders the profiles and the computer levels ordered by rating.
+    fn view_leaderboard(&self) -> Html {
+        let mut entries: Vec<(String, f64, String)> = self.profiles.iter()
+            .map(|profile| (profile.name.clone(), profile.rating, profile.games.len().to_string()))
+            .collect();
+        entries.extend(self.ai_ratings.iter()
+            .map(|ai_rating| (profile::get_opponent_name(Some(ai_rating.ai_level)), ai_rating.rating, "-".to_string())));
+        entries.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        html! {
+            <>
+            <table class="scores">
+                <caption>{ tr("stats.leaderboard") }</caption>
+                <tr>
+                    <th>{ "#" }</th>
+                    <th>{ tr("stats.name") }</th>
+                    <th>{ tr("stats.rating") }</th>
+                    <th>{ tr("stats.games") }</th>
+                </tr>
+                { for entries.iter().enumerate().map(|(idx, (name, rating, games))| html! {
+                    <tr>
+                        <td>{ idx + 1 }</td>
+                        <td>{ name.clone() }</td>
+                        <td>{ format!("{:.0}", rating) }</td>
+                        <td>{ games.clone() }</td>
+                    </tr>
+                }) }
+            </table>
+            <button disabled=self.calibration.is_some() onclick=self.link.callback(|_| Msg::AiRecalibrated)>
+                { tr("stats.recalibrate") }
+            </button>
+            {
+                match &self.calibration {
+                    None => { html! {} }
+                    Some(calibration) => {
+                        let (num_played, num_games) = calibration.get_progress();
+                        let text_progress: String = trf(
+                            "stats.calibration_progress",
+                            &[num_played.to_string().as_str(), num_games.to_string().as_str()],
+                        );
+                        html! { <span>{ format!(" {}", text_progress) }</span> }
+                    }
+                }
+            }
+            </>
+        }
+    }
+}
+
+/// Renders the header of the table of summaries.
+fn view_summary_header(caption_rules: &str) -> Html {
+    html! {
+        <tr>
+            <th>{ tr("stats.opponent") }</th>
+            <th>{ caption_rules }</th>
+            <th>{ tr("stats.games") }</th>
+            <th>{ tr("stats.wins") }</th>
+            <th>{ tr("stats.losses") }</th>
+            <th>{ tr("analysis.accuracy") }</th>
+            <th>{ tr("stats.best_time") }</th>
+        </tr>
+    }
+}
+
+/// Renders the row of the table of summaries.
+fn view_summary_row(opponent: &str, rules: &str, summary: &Summary) -> Html {
+    html! {
+        <tr>
+            <td>{ opponent }</td>
+            <td>{ rules }</td>
+            <td>{ summary.games }</td>
+            <td>{ summary.wins }</td>
+            <td>{ summary.get_losses() }</td>
+            <td>{ format!("{:.0}%", summary.get_accuracy() * 100.0) }</td>
+            <td>{ summary.best_time.map_or("-".to_string(), format_seconds) }</td>
+        </tr>
+    }
+}
+
+/// Renders the line chart of the values from 0 to 1 in the order of the games.
+fn view_chart(caption: &str, values: &[f64]) -> Html {
+    let step: f64 = if values.len() > 1 { CHART_WIDTH / (values.len() - 1) as f64 } else { 0.0 };
+    let points: String = values.iter().enumerate()
+        .map(|(idx, value)| format!("{:.1},{:.1}", idx as f64 * step, CHART_HEIGHT * (1.0 - value.clamp(0.0, 1.0))))
+        .collect::<Vec<String>>()
+        .join(" ");
+    html! {
+        <div class="chart">
+            <h2>{ caption }</h2>
+            <svg width=CHART_WIDTH.to_string() height=CHART_HEIGHT.to_string()
+                viewBox=format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT)>
+                <line class="chart-axis" x1="0" y1=(CHART_HEIGHT / 2.0).to_string()
+                    x2=CHART_WIDTH.to_string() y2=(CHART_HEIGHT / 2.0).to_string() />
+                <polyline class="chart-line" points=points />
+            </svg>
+        </div>
+    }
+}
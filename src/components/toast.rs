@@ -0,0 +1,127 @@
+use yew::{ComponentLink, Component, Html, html, classes, Properties};
+use yew::services::TimeoutService;
+use yew::services::timeout::TimeoutTask;
+use std::collections::HashSet;
+use std::time::Duration;
+use crate::i18n::tr;
+
+// TOAST_SECONDS: How long a notification stays on the screen.
+const TOAST_SECONDS: u64 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Error,
+}
+
+impl ToastKind {
+    fn get_class(&self) -> &'static str {
+        match self {
+            ToastKind::Info => "toast-info",
+            ToastKind::Success => "toast-success",
+            ToastKind::Error => "toast-error",
+        }
+    }
+}
+
+/// A short notification that disappears by itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    // id: The unique number of the notification, by which it is hidden.
+    pub id: usize,
+    pub text: String,
+    pub kind: ToastKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct ToastListProps {
+    pub toasts: Vec<Toast>,
+}
+
+pub enum Msg {
+    // Hiding the notification with the id, on time or by the button.
+    Hidden(usize),
+}
+
+/// Shows the notifications in the corner of the page without blocking the game.
+pub struct ToastList {
+    link: ComponentLink<Self>,
+    props: ToastListProps,
+    // hidden: The ids of the shown notifications that were hidden before they left the list.
+    hidden: HashSet<usize>,
+    // timers: The timers of the shown notifications by their ids.
+    timers: Vec<(usize, TimeoutTask)>,
+}
+
+impl Component for ToastList {
+    type Message = Msg;
+    type Properties = ToastListProps;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut toast_list = Self {
+            link,
+            props,
+            hidden: HashSet::new(),
+            timers: Vec::new(),
+        };
+        toast_list.start_timers();
+        toast_list
+    }
+
+    fn update(&mut self, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Hidden(id) => {
+                self.hidden.insert(id);
+                self.timers.retain(|(id_timer, _)| *id_timer != id);
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> bool {
+        self.props = props;
+        let toasts: &Vec<Toast> = &self.props.toasts;
+        self.hidden.retain(|id| toasts.iter().any(|toast| toast.id == *id));
+        self.start_timers();
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            // The shots and the turns are already announced by the board, only the errors are read out.
+            <div class="toasts" aria-live="off">
+                { for self.props.toasts.iter()
+                    .filter(|toast| !self.hidden.contains(&toast.id))
+                    .map(|toast| {
+                        let id: usize = toast.id;
+                        let role: Option<&str> = if toast.kind == ToastKind::Error { Some("alert") } else { None };
+                        html! {
+                            <div class=classes!("toast", toast.kind.get_class()) role=role>
+                                <span>{ toast.text.clone() }</span>
+                                <button class="toast-close" aria-label=tr("toast.close")
+                                    onclick=self.link.callback(move |_| Msg::Hidden(id))>{ "\u{d7}" }</button>
+                            </div>
+                        }
+                    }) }
+            </div>
+        }
+    }
+}
+
+impl ToastList {
+    /// Starts the timers of the new notifications.
+    fn start_timers(&mut self) {
+        for toast in self.props.toasts.iter() {
+            let id: usize = toast.id;
+            if self.hidden.contains(&id) || self.timers.iter().any(|(id_timer, _)| *id_timer == id) {
+                continue;
+            }
+            let task: TimeoutTask = TimeoutService::spawn(
+                Duration::from_secs(TOAST_SECONDS),
+                self.link.callback(move |_| Msg::Hidden(id)),
+            );
+            self.timers.push((id, task));
+        }
+    }
+}
@@ -0,0 +1,535 @@
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+/// Languages of the interface
+pub enum Language {
+    English,
+    Russian,
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Language::English
+    }
+}
+
+impl Language {
+    pub fn all() -> Vec<Language> {
+        vec![Language::English, Language::Russian]
+    }
+
+    /// Returns the code of the language used in the form.
+    pub fn get_id(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Russian => "ru",
+        }
+    }
+
+    /// Returns the name of the language in the language itself.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Russian => "Русский",
+        }
+    }
+
+    /// Returns the index of the plural form for the number:
+    /// "one" and "other" in English, "one", "few" and "many" in Russian.
+    fn get_plural_form(&self, count: usize) -> usize {
+        match self {
+            Language::English => {
+                if count == 1 { 0 } else { 1 }
+            }
+            Language::Russian => {
+                if count % 10 == 1 && count % 100 != 11 {
+                    0
+                } else if (2..=4).contains(&(count % 10)) && !(12..=14).contains(&(count % 100)) {
+                    1
+                } else {
+                    2
+                }
+            }
+        }
+    }
+}
+
+thread_local! {
+    // LANGUAGE: The language in which the messages are shown now.
+    static LANGUAGE: Cell<Language> = Cell::new(Language::English);
+}
+
+pub fn set_language(language: Language) {
+    LANGUAGE.with(|current| current.set(language));
+}
+
+pub fn get_language() -> Language {
+    LANGUAGE.with(|current| current.get())
+}
+
+/// Returns the message with the key in the current language,
+/// in English if it is not translated, or the key itself if there is no such message.
+pub fn tr(key: &str) -> String {
+    get_message(get_language(), key)
+        .or_else(|| get_message(Language::English, key))
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// Returns the message with the key where every "{}" is replaced by the next argument.
+/// The placeholders left without an argument stay as they are.
+pub fn trf(key: &str, args: &[&str]) -> String {
+    let template: String = tr(key);
+    let mut parts = template.split("{}");
+    let mut text: String = parts.next().unwrap_or("").to_string();
+    let mut args = args.iter();
+    for part in parts {
+        text.push_str(args.next().map_or("{}", |arg| *arg));
+        text.push_str(part);
+    }
+    text
+}
+
+/// Returns the form of the message with the key for the number, with the number in place of "{}".
+/// The forms of the message are separated by "|".
+pub fn tr_count(key: &str, count: usize) -> String {
+    let language: Language = get_language();
+    let (language, text) = match get_message(language, key) {
+        Some(text) => { (language, text) }
+        None => { (Language::English, get_message(Language::English, key).unwrap_or(key)) }
+    };
+    let forms: Vec<&str> = text.split('|').collect();
+    let idx_form: usize = language.get_plural_form(count).min(forms.len() - 1);
+    forms[idx_form].replacen("{}", count.to_string().as_str(), 1)
+}
+
+fn get_message(language: Language, key: &str) -> Option<&'static str> {
+    match language {
+        Language::English => get_english(key),
+        Language::Russian => get_russian(key),
+    }
+}
+
+fn get_english(key: &str) -> Option<&'static str> {
+    let text: &str = match key {
+        "main.statistics" => "Statistics",
+        "main.back" => "Back to the game",
+        "settings.show" => "Show Settings",
+        "settings.hide" => "Hide Settings",
+        "settings.player" => "Player {}",
+        "settings.control" => "Control type",
+        "settings.manual" => "Manual control",
+        "settings.computer_easy" => "Computer: easy",
+        "settings.computer_normal" => "Computer: normal",
+        "settings.computer_hard" => "Computer: hard",
+        "settings.team" => "Team",
+        "settings.no_team" => "No team",
+        "settings.team_a" => "Team A",
+        "settings.team_b" => "Team B",
+        "settings.team_number" => "Team {}",
+        "settings.remove" => "Remove",
+        "settings.add_player" => "Add player",
+        "settings.two_vs_two" => "2 vs 2",
+        "settings.new_profile" => "New profile",
+        "settings.create" => "Create",
+        "settings.game_mode" => "Game mode",
+        "settings.mode_battle" => "Battle",
+        "settings.mode_solo" => "Solo challenge",
+        "settings.mode_daily" => "Daily challenge",
+        "settings.mode_puzzle" => "Puzzle",
+        "settings.field_size" => "Field size",
+        "settings.moving_fleet" => "Moving fleet",
+        "settings.ai_level_auto" => "Pick the computer level by rating",
+        "settings.fleet" => "Fleet",
+        "settings.fleet_classic" => "Classic",
+        "settings.fleet_shapes" => "Shapes",
+        "settings.theme" => "Theme",
+        "settings.theme_light" => "Light",
+        "settings.theme_dark" => "Dark",
+        "settings.theme_high_contrast" => "High contrast",
+        "settings.theme_color_blind" => "Colour-blind safe",
+        "settings.glyphs" => "Mark the cells with symbols",
+        "settings.language" => "Language",
+        "settings.submit" => "Submit",
+        "settings.computer_name" => "Computer {}",
+        "settings.rules_classic" => "{} classic fleet",
+        "settings.rules_shapes" => "{} shapes fleet",
+        "settings.rules_custom" => "{} custom fleet",
+        "settings.rules_moving" => "{}, moving",
+        "board.field" => "{} Field",
+        "board.field_eliminated" => "{} Field (eliminated)",
+        "board.ships_left" => "{} ship left|{} ships left",
+        "board.heatmap" => "Show probabilities of decks",
+        "board.keys" => "Keys: arrows move, Enter or Space fires, PageUp/PageDown switch the field, \
+                         Shift with arrows moves the selected ship, R rotates it, N starts a new game after the end, \
+                         or type a cell like E5",
+        "board.shoot_at" => "Shoot at ",
+        "board.zoom_in" => "Zoom in",
+        "board.zoom_out" => "Zoom out",
+        "board.annotate" => "Right-click or hold a cell of the opponent to mark it as water, \
+                             as a suspected ship, or to clear the mark",
+        "board.game_boards" => "Game boards",
+        "board.select_ship" => "Select an undamaged ship to move it instead of a shot",
+        "board.selected_ship" => "Selected ship: {}",
+        "board.turn" => "{}'s turn",
+        "board.shot" => "{} fired at {} on the field of {}: {}.",
+        "board.won" => "{} won!",
+        "board.game_over" => "Game over",
+        "shot.miss" => "miss",
+        "shot.hit" => "hit",
+        "shot.sunk" => "sunk ({}-decker)",
+        "cell.unknown" => "unknown",
+        "cell.water" => "water",
+        "cell.ship" => "ship",
+        "cell.sunk" => "sunk",
+        "cell.marked_water" => "marked as water",
+        "cell.marked_ship" => "marked as ship",
+        "history.title" => "History",
+        "history.final" => "Final position",
+        "history.final_ships" => "The ships are shown where they stood at the end of the game.",
+        "analysis.title" => "Analysis",
+        "analysis.player" => "Player",
+        "analysis.shots" => "Shots",
+        "analysis.hits" => "{} hit|{} hits",
+        "analysis.accuracy" => "Accuracy",
+        "analysis.first_hit" => "Shots to first hit",
+        "analysis.to_sink" => "Shots to sink by size",
+        "analysis.miss_streak" => "Longest miss streak",
+        "coach.title" => "Shot coach: {}",
+        "coach.summary" => "Good: {}  Inaccuracies: {}  Blunders: {}",
+        "coach.mistake" => "{} on {}'s field: {}",
+        "coach.best" => "{} (best {} at {})",
+        "coach.good" => "good",
+        "coach.inaccuracy" => "inaccuracy",
+        "coach.blunder" => "blunder",
+        "board.rematch" => "Rematch",
+        "board.review" => "Review the game",
+        "toast.close" => "Close",
+        "fleet.title" => "Fleet",
+        "fleet.sunk" => "sunk",
+        "fleet.hits" => "{} of {} decks hit",
+        "fleet.remaining" => "Ships afloat by size: {}",
+        "toast.sunk" => "{} sank a {}-decker of {}",
+        "toast.cannot_shoot" => "You cannot shoot at this cell",
+        "toast.bad_cell" => "\"{}\" is not a cell of the field",
+        "ai.easy" => "Easy",
+        "ai.normal" => "Normal",
+        "ai.hard" => "Hard",
+        "shape.straight" => "{}-deck",
+        "shape.l" => "L-shape",
+        "shape.t" => "T-shape",
+        "shape.square" => "Square",
+        "shape.plus" => "Plus",
+        "solo.title" => "Solo challenge",
+        "solo.daily_title" => "Daily challenge {}",
+        "solo.status" => "Shots: {}  Par: {}  Time: {}",
+        "solo.result" => "The fleet is sunk in {} shots ({}) and {}",
+        "solo.practice" => "Today's result is already recorded, this game is a practice.",
+        "solo.best_results" => "Best results: {}, {}",
+        "solo.fewest_shots" => "Fewest shots",
+        "solo.fastest" => "Fastest",
+        "solo.share" => "Share your result",
+        "solo.daily_results" => "Daily challenges",
+        "solo.date" => "Date",
+        "solo.shots" => "Shots",
+        "solo.par" => "Par",
+        "solo.time" => "Time",
+        "solo.even_par" => "par",
+        "solo.share_title" => "Sea Battle daily {}",
+        "solo.share_summary" => "{} shots ({}) {}",
+        "puzzle.easy" => "Easy",
+        "puzzle.medium" => "Medium",
+        "puzzle.hard" => "Hard",
+        "puzzle.status" => "Difficulty: {}  Fleet: {}",
+        "puzzle.hint" => "Hint",
+        "puzzle.check" => "Check",
+        "puzzle.new" => "New puzzle",
+        "puzzle.solved" => "Solved!",
+        "puzzle.no_mistakes" => "No mistakes so far.",
+        "puzzle.mistakes" => "Mistakes: {}",
+        "puzzle.hint_ship" => "The highlighted cell {} is a ship segment: {}.",
+        "puzzle.hint_water" => "The highlighted cell {} is water: {}.",
+        "puzzle.reason_mistake" => "this cell was marked incorrectly",
+        "puzzle.reason_line_full" => "all decks of its row or column are already found",
+        "puzzle.reason_line_needs_all" => "its row or column needs all remaining cells",
+        "puzzle.reason_near_ship" => "it touches a ship segment diagonally",
+        "puzzle.reason_no_ship_fits" => "none of the ships can fit there",
+        "puzzle.reason_solution" => "taken from the solution",
+        "stats.no_games" => "No games have been played yet.",
+        "stats.export" => "Export",
+        "stats.import" => "Import",
+        "stats.import_failed" => "The text is not a valid export of profiles.",
+        "stats.imported" => "Imported profiles: {}",
+        "stats.lifetime" => "Lifetime",
+        "stats.all_games" => "All games",
+        "stats.by_opponent" => "By opponent and rules",
+        "stats.rules" => "Rules",
+        "stats.win_rate" => "Share of wins",
+        "stats.leaderboard" => "Leaderboard",
+        "stats.name" => "Name",
+        "stats.rating" => "Rating",
+        "stats.games" => "Games",
+        "stats.opponent" => "Opponent",
+        "stats.wins" => "Wins",
+        "stats.losses" => "Losses",
+        "stats.best_time" => "Best time",
+        "stats.humans" => "Humans",
+        "stats.computer" => "Computer ({})",
+        "stats.recalibrate" => "Recalibrate computer ratings",
+        "stats.calibration_progress" => "Games played: {} of {}",
+        "achievement.title" => "Achievements",
+        "achievement.unlocked" => "{} unlocked \"{}\"!",
+        "achievement.first_hit" => "First blood",
+        "achievement.hot_streak" => "Hot streak",
+        "achievement.surgical_strike" => "Surgical strike",
+        "achievement.flawless_victory" => "Flawless victory",
+        "achievement.sharpshooter" => "Sharpshooter",
+        "achievement.first_hit_description" => "Hit an enemy ship",
+        "achievement.hot_streak_description" => "Hit {} time in a row|Hit {} times in a row",
+        "achievement.surgical_strike_description" => "Sink a 4-decker in 4 shots",
+        "achievement.flawless_victory_description" => "Win without losing a ship",
+        "achievement.sharpshooter_description" => "Win with at least {}% accuracy",
+        _ => { return None; }
+    };
+    Some(text)
+}
+
+fn get_russian(key: &str) -> Option<&'static str> {
+    let text: &str = match key {
+        "main.statistics" => "Статистика",
+        "main.back" => "Вернуться к игре",
+        "settings.show" => "Показать настройки",
+        "settings.hide" => "Скрыть настройки",
+        "settings.player" => "Игрок {}",
+        "settings.control" => "Управление",
+        "settings.manual" => "Ручное управление",
+        "settings.computer_easy" => "Компьютер: лёгкий",
+        "settings.computer_normal" => "Компьютер: средний",
+        "settings.computer_hard" => "Компьютер: сложный",
+        "settings.team" => "Команда",
+        "settings.no_team" => "Без команды",
+        "settings.team_a" => "Команда A",
+        "settings.team_b" => "Команда B",
+        "settings.team_number" => "Команда {}",
+        "settings.remove" => "Удалить",
+        "settings.add_player" => "Добавить игрока",
+        "settings.two_vs_two" => "2 на 2",
+        "settings.new_profile" => "Новый профиль",
+        "settings.create" => "Создать",
+        "settings.game_mode" => "Режим игры",
+        "settings.mode_battle" => "Сражение",
+        "settings.mode_solo" => "Одиночное испытание",
+        "settings.mode_daily" => "Испытание дня",
+        "settings.mode_puzzle" => "Головоломка",
+        "settings.field_size" => "Размер поля",
+        "settings.moving_fleet" => "Подвижный флот",
+        "settings.ai_level_auto" => "Подбирать уровень компьютера по рейтингу",
+        "settings.fleet" => "Флот",
+        "settings.fleet_classic" => "Классический",
+        "settings.fleet_shapes" => "Фигуры",
+        "settings.theme" => "Тема",
+        "settings.theme_light" => "Светлая",
+        "settings.theme_dark" => "Тёмная",
+        "settings.theme_high_contrast" => "Высокий контраст",
+        "settings.theme_color_blind" => "Для дальтоников",
+        "settings.glyphs" => "Отмечать клетки символами",
+        "settings.language" => "Язык",
+        "settings.submit" => "Применить",
+        "settings.computer_name" => "Компьютер {}",
+        "settings.rules_classic" => "{}, классический флот",
+        "settings.rules_shapes" => "{}, флот из фигур",
+        "settings.rules_custom" => "{}, свой флот",
+        "settings.rules_moving" => "{}, подвижный",
+        "board.field" => "Поле: {}",
+        "board.field_eliminated" => "Поле: {} (выбыл)",
+        "board.ships_left" => "остался {} корабль|осталось {} корабля|осталось {} кораблей",
+        "board.heatmap" => "Показывать вероятности палуб",
+        "board.keys" => "Клавиши: стрелки двигают курсор, Enter или пробел — выстрел, PageUp/PageDown — \
+                         другое поле, Shift со стрелками двигает выбранный корабль, R поворачивает его, \
+                         N — новая игра после окончания, или наберите клетку, например E5",
+        "board.shoot_at" => "Выстрел по ",
+        "board.zoom_in" => "Увеличить",
+        "board.zoom_out" => "Уменьшить",
+        "board.annotate" => "Щёлкните правой кнопкой или удерживайте клетку противника, чтобы отметить \
+                             её как воду, как возможный корабль или снять отметку",
+        "board.game_boards" => "Игровые поля",
+        "board.select_ship" => "Выберите целый корабль, чтобы сдвинуть его вместо выстрела",
+        "board.selected_ship" => "Выбран корабль: {}",
+        "board.turn" => "Ходит {}",
+        "board.shot" => "{} стреляет по {} на поле {}: {}.",
+        "board.won" => "Победа: {}!",
+        "board.game_over" => "Игра окончена",
+        "shot.miss" => "мимо",
+        "shot.hit" => "попадание",
+        "shot.sunk" => "потоплен ({}-палубный)",
+        "cell.unknown" => "неизвестно",
+        "cell.water" => "вода",
+        "cell.ship" => "корабль",
+        "cell.sunk" => "потоплен",
+        "cell.marked_water" => "отмечена как вода",
+        "cell.marked_ship" => "отмечена как корабль",
+        "history.title" => "История",
+        "history.final" => "Итоговая позиция",
+        "history.final_ships" => "Корабли показаны там, где они стояли в конце игры.",
+        "analysis.title" => "Разбор",
+        "analysis.player" => "Игрок",
+        "analysis.shots" => "Выстрелы",
+        "analysis.hits" => "{} попадание|{} попадания|{} попаданий",
+        "analysis.accuracy" => "Точность",
+        "analysis.first_hit" => "Выстрелов до первого попадания",
+        "analysis.to_sink" => "Выстрелов до потопления по размеру",
+        "analysis.miss_streak" => "Самая длинная серия промахов",
+        "coach.title" => "Тренер стрельбы: {}",
+        "coach.summary" => "Хорошие: {}  Неточности: {}  Грубые ошибки: {}",
+        "coach.mistake" => "{} на поле {}: {}",
+        "coach.best" => "{} (лучше {} на {})",
+        "coach.good" => "хороший",
+        "coach.inaccuracy" => "неточность",
+        "coach.blunder" => "грубая ошибка",
+        "board.rematch" => "Реванш",
+        "board.review" => "Разобрать игру",
+        "toast.close" => "Закрыть",
+        "fleet.title" => "Флот",
+        "fleet.sunk" => "потоплен",
+        "fleet.hits" => "подбито палуб: {} из {}",
+        "fleet.remaining" => "На плаву корабли размеров: {}",
+        "toast.sunk" => "{} топит {}-палубный корабль: {}",
+        "toast.cannot_shoot" => "По этой клетке стрелять нельзя",
+        "toast.bad_cell" => "«{}» — не клетка поля",
+        "ai.easy" => "Лёгкий",
+        "ai.normal" => "Средний",
+        "ai.hard" => "Сложный",
+        "shape.straight" => "{}-палубный",
+        "shape.l" => "Г-образный",
+        "shape.t" => "Т-образный",
+        "shape.square" => "Квадрат",
+        "shape.plus" => "Крест",
+        "solo.title" => "Одиночное испытание",
+        "solo.daily_title" => "Испытание дня {}",
+        "solo.status" => "Выстрелы: {}  Пар: {}  Время: {}",
+        "solo.result" => "Флот потоплен за {} выстр. ({}) и {}",
+        "solo.practice" => "Сегодняшний результат уже записан, эта игра — тренировка.",
+        "solo.best_results" => "Лучшие результаты: {}, {}",
+        "solo.fewest_shots" => "Меньше всего выстрелов",
+        "solo.fastest" => "Быстрее всего",
+        "solo.share" => "Поделитесь результатом",
+        "solo.daily_results" => "Испытания дня",
+        "solo.date" => "Дата",
+        "solo.shots" => "Выстрелы",
+        "solo.par" => "Пар",
+        "solo.time" => "Время",
+        "solo.even_par" => "пар",
+        "solo.share_title" => "Морской бой, испытание {}",
+        "solo.share_summary" => "{} выстр. ({}) {}",
+        "puzzle.easy" => "Лёгкая",
+        "puzzle.medium" => "Средняя",
+        "puzzle.hard" => "Сложная",
+        "puzzle.status" => "Сложность: {}  Флот: {}",
+        "puzzle.hint" => "Подсказка",
+        "puzzle.check" => "Проверить",
+        "puzzle.new" => "Новая головоломка",
+        "puzzle.solved" => "Решено!",
+        "puzzle.no_mistakes" => "Пока ошибок нет.",
+        "puzzle.mistakes" => "Ошибок: {}",
+        "puzzle.hint_ship" => "Выделенная клетка {} — часть корабля: {}.",
+        "puzzle.hint_water" => "Выделенная клетка {} — вода: {}.",
+        "puzzle.reason_mistake" => "эта клетка была отмечена неверно",
+        "puzzle.reason_line_full" => "все палубы её строки или столбца уже найдены",
+        "puzzle.reason_line_needs_all" => "её строке или столбцу нужны все оставшиеся клетки",
+        "puzzle.reason_near_ship" => "она касается части корабля по диагонали",
+        "puzzle.reason_no_ship_fits" => "туда не помещается ни один корабль",
+        "puzzle.reason_solution" => "взято из решения",
+        "stats.no_games" => "Ещё не сыграно ни одной игры.",
+        "stats.export" => "Экспорт",
+        "stats.import" => "Импорт",
+        "stats.import_failed" => "Текст не является экспортом профилей.",
+        "stats.imported" => "Импортировано профилей: {}",
+        "stats.lifetime" => "За всё время",
+        "stats.all_games" => "Все игры",
+        "stats.by_opponent" => "По соперникам и правилам",
+        "stats.rules" => "Правила",
+        "stats.win_rate" => "Доля побед",
+        "stats.leaderboard" => "Таблица лидеров",
+        "stats.name" => "Имя",
+        "stats.rating" => "Рейтинг",
+        "stats.games" => "Игры",
+        "stats.opponent" => "Соперник",
+        "stats.wins" => "Победы",
+        "stats.losses" => "Поражения",
+        "stats.best_time" => "Лучшее время",
+        "stats.humans" => "Люди",
+        "stats.computer" => "Компьютер ({})",
+        "stats.recalibrate" => "Пересчитать рейтинги компьютера",
+        "stats.calibration_progress" => "Сыграно игр: {} из {}",
+        "achievement.title" => "Достижения",
+        "achievement.unlocked" => "{} получает «{}»!",
+        "achievement.first_hit" => "Первая кровь",
+        "achievement.hot_streak" => "Серия попаданий",
+        "achievement.surgical_strike" => "Точечный удар",
+        "achievement.flawless_victory" => "Чистая победа",
+        "achievement.sharpshooter" => "Снайпер",
+        "achievement.first_hit_description" => "Попасть во вражеский корабль",
+        "achievement.hot_streak_description" => "Попасть {} раз подряд|Попасть {} раза подряд|Попасть {} раз подряд",
+        "achievement.surgical_strike_description" => "Потопить 4-палубный корабль за 4 выстрела",
+        "achievement.flawless_victory_description" => "Победить, не потеряв ни одного корабля",
+        "achievement.sharpshooter_description" => "Победить с точностью не ниже {}%",
+        _ => { return None; }
+    };
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chooses_russian_plural_forms() {
+        let forms: Vec<(usize, usize)> = vec![
+            (0, 2), (1, 0), (2, 1), (4, 1), (5, 2), (11, 2), (12, 2), (14, 2),
+            (20, 2), (21, 0), (22, 1), (25, 2), (101, 0), (111, 2), (112, 2), (122, 1),
+        ];
+        for (count, idx_form) in forms {
+            assert_eq!(Language::Russian.get_plural_form(count), idx_form, "count {}", count);
+        }
+    }
+
+    #[test]
+    fn chooses_english_plural_forms() {
+        assert_eq!(Language::English.get_plural_form(0), 1);
+        assert_eq!(Language::English.get_plural_form(1), 0);
+        assert_eq!(Language::English.get_plural_form(2), 1);
+        assert_eq!(Language::English.get_plural_form(21), 1);
+    }
+
+    #[test]
+    fn puts_number_into_plural_form() {
+        set_language(Language::Russian);
+        assert_eq!(tr_count("board.ships_left", 1), "остался 1 корабль");
+        assert_eq!(tr_count("board.ships_left", 3), "осталось 3 корабля");
+        assert_eq!(tr_count("board.ships_left", 11), "осталось 11 кораблей");
+        assert_eq!(tr_count("board.ships_left", 21), "остался 21 корабль");
+        set_language(Language::English);
+        assert_eq!(tr_count("board.ships_left", 1), "1 ship left");
+        assert_eq!(tr_count("board.ships_left", 21), "21 ships left");
+    }
+
+    #[test]
+    fn substitutes_arguments_in_order() {
+        set_language(Language::English);
+        assert_eq!(trf("board.shot", &["Ann", "C5", "Bob", "miss"]), "Ann fired at C5 on the field of Bob: miss.");
+        // The missing arguments leave the placeholders, the extra ones are ignored.
+        assert_eq!(trf("fleet.hits", &["2"]), "2 of {} decks hit");
+        assert_eq!(trf("board.won", &["Ann", "Bob"]), "Ann won!");
+        // The braces inside an argument are not taken for a placeholder.
+        assert_eq!(trf("board.shot", &["{}", "C5", "Bob", "hit"]), "{} fired at C5 on the field of Bob: hit.");
+    }
+
+    #[test]
+    fn falls_back_to_english_and_to_key() {
+        set_language(Language::Russian);
+        assert_eq!(tr("shot.miss"), "мимо");
+        assert_eq!(tr("no.such.key"), "no.such.key");
+        assert_eq!(tr_count("no.such.key", 5), "no.such.key");
+    }
+}
@@ -1,27 +1,38 @@
-use yew::{Component, ComponentLink, Html, html, Callback};
+use yew::{Component, ComponentLink, Html, html, Callback, classes};
 
-use components::play_board;
+use components::{play_board, solo_board, puzzle_board, stats_page, achievements_panel};
+use objects::shape::get_classic_fleet;
+use objects::player::AiLevel;
+use objects::profile::DEFAULT_PROFILE_NAME;
 
 
 mod agents;
 mod components;
 mod objects;
 mod settings;
+mod storage;
+mod i18n;
 
 pub enum MsgGame {
     RestartingWithNewSettings((
-                                  settings::PlayerSetting,
-                                  settings::PlayerSetting,
-                                  settings::FieldSettings
-                              ))
+                                  Vec<settings::PlayerSetting>,
+                                  settings::FieldSettings,
+                                  settings::RulesSettings
+                              )),
+    // Switching between the game and the statistics of the profiles.
+    StatsToggled,
+    DisplayChanged(settings::DisplaySettings),
 }
 
 pub struct GameSeaBattle {
     link: ComponentLink<Self>,
-    first_player_settings: settings::PlayerSetting,
-    second_player_settings: settings::PlayerSetting,
+    players_settings: Vec<settings::PlayerSetting>,
     field_settings: settings::FieldSettings,
-
+    rules_settings: settings::RulesSettings,
+    // game_number: Changes on every restart, so that the board is created anew.
+    game_number: usize,
+    is_stats_shown: bool,
+    display_setting: settings::DisplaySettings,
 }
 
 impl Component for GameSeaBattle {
@@ -29,33 +40,60 @@ impl Component for GameSeaBattle {
     type Properties = ();
 
     fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let display_setting: settings::DisplaySettings = settings::load_display_settings();
+        i18n::set_language(display_setting.language);
         Self {
             link,
-            first_player_settings: settings::PlayerSetting {
-                name: "Player".to_string(),
-                is_manual_control: true,
-            },
-            second_player_settings: settings::PlayerSetting {
-                name: "Computer".to_string(),
-                is_manual_control: false,
-            },
+            players_settings: vec![
+                settings::PlayerSetting {
+                    name: DEFAULT_PROFILE_NAME.to_string(),
+                    is_manual_control: true,
+                    ai_level: AiLevel::Hard,
+                    team: None,
+                },
+                settings::PlayerSetting {
+                    name: settings::get_computer_name(1),
+                    is_manual_control: false,
+                    ai_level: AiLevel::Hard,
+                    team: None,
+                },
+            ],
             field_settings: settings::FieldSettings {
                 width_field: 10,
                 height_field: 10,
+                fleet: get_classic_fleet(),
+            },
+            rules_settings: settings::RulesSettings {
+                is_moving_fleet: false,
+                mode: settings::GameMode::Battle,
+                is_ai_level_auto: false,
             },
+            game_number: 0,
+            is_stats_shown: false,
+            display_setting,
         }
     }
 
     fn update(&mut self, msg: Self::Message) -> bool {
         match msg {
             MsgGame::RestartingWithNewSettings((
-                                                   first_player_settings,
-                                                   second_player_settings,
-                                                   field_setting
+                                                   players_settings,
+                                                   field_setting,
+                                                   rules_setting
                                                )) => {
-                self.first_player_settings = first_player_settings;
-                self.second_player_settings = second_player_settings;
+                self.players_settings = players_settings;
                 self.field_settings = field_setting;
+                self.rules_settings = rules_setting;
+                self.game_number += 1;
+                self.is_stats_shown = false;
+            }
+            MsgGame::StatsToggled => {
+                self.is_stats_shown = !self.is_stats_shown;
+            }
+            MsgGame::DisplayChanged(display_setting) => {
+                settings::save_display_settings(&display_setting);
+                i18n::set_language(display_setting.language);
+                self.display_setting = display_setting;
             }
         }
         true
@@ -66,31 +104,87 @@ impl Component for GameSeaBattle {
     }
 
     fn view(&self) -> Html {
-        let parent_call: Callback<(settings::PlayerSetting,
-                                   settings::PlayerSetting,
-                                   settings::FieldSettings)> = self.link.callback(
+        let parent_call: Callback<(Vec<settings::PlayerSetting>,
+                                   settings::FieldSettings,
+                                   settings::RulesSettings)> = self.link.callback(
             MsgGame::RestartingWithNewSettings
         );
 
 
+        let class_glyphs: Option<&str> = if self.display_setting.is_glyphs_shown { Some("glyphs") } else { None };
         html! {
-            <>
+            <div class=classes!("app", format!("theme-{}", self.display_setting.theme.get_id()), class_glyphs)>
             <div class="form_container">
                 <settings::SettingsForm
-                    first_player_settings=self.first_player_settings.clone()
-                    second_player_settings=self.second_player_settings.clone()
+                    players_settings=self.players_settings.clone()
                     field_setting=self.field_settings.clone()
+                    rules_setting=self.rules_settings.clone()
                     parent_call=parent_call.clone()
+                    display_setting=self.display_setting.clone()
+                    display_call=self.link.callback(MsgGame::DisplayChanged)
                 />
             </div>
+            <div class="wrapper">
+                <div>
+                    <button onclick=self.link.callback(|_| MsgGame::StatsToggled)>
+                        { if self.is_stats_shown { i18n::tr("main.back") } else { i18n::tr("main.statistics") } }
+                    </button>
+                </div>
+            </div>
+
+            {
+                if self.is_stats_shown {
+                    html! { <stats_page::StatsPage /> }
+                } else {
+                    html! {}
+                }
+            }
+            // The board stays while the statistics are shown, so that the game goes on.
+            // The panel is created before the board to receive the start of the game.
+            <div hidden=self.is_stats_shown>
+                <div hidden=self.rules_settings.mode != settings::GameMode::Battle>
+                    <achievements_panel::AchievementsPanel />
+                </div>
+                { self.view_board(parent_call) }
+            </div>
+            </div>
+        }
+    }
+}
 
-            <play_board::PlayBoard
-                first_player_settings=self.first_player_settings.clone()
-                second_player_settings=self.second_player_settings.clone()
-                field_setting=self.field_settings.clone()
-                parent_call=parent_call.clone()
-            />
-            </>
+impl GameSeaBattle {
+    /// Renders the board of the chosen game mode.
+    fn view_board(&self, parent_call: Callback<(Vec<settings::PlayerSetting>,
+                                                settings::FieldSettings,
+                                                settings::RulesSettings)>) -> Html {
+        match self.rules_settings.mode {
+            settings::GameMode::Battle => html! {
+                <play_board::PlayBoard
+                    key=self.game_number
+                    players_settings=self.players_settings.clone()
+                    field_setting=self.field_settings.clone()
+                    rules_setting=self.rules_settings.clone()
+                    parent_call=parent_call.clone()
+                />
+            },
+            settings::GameMode::Solo | settings::GameMode::Daily => html! {
+                <solo_board::SoloBoard
+                    key=self.game_number
+                    players_settings=self.players_settings.clone()
+                    field_setting=self.field_settings.clone()
+                    rules_setting=self.rules_settings.clone()
+                    parent_call=parent_call.clone()
+                />
+            },
+            settings::GameMode::Puzzle => html! {
+                <puzzle_board::PuzzleBoard
+                    key=self.game_number
+                    players_settings=self.players_settings.clone()
+                    field_setting=self.field_settings.clone()
+                    rules_setting=self.rules_settings.clone()
+                    parent_call=parent_call.clone()
+                />
+            },
         }
     }
 }
@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use crate::agents::event_bus::GameEvent;
+use crate::storage;
+use crate::i18n::{tr, trf, tr_count};
+
+// KEY_ACHIEVEMENTS: The key of the unlocked achievements in the local storage.
+const KEY_ACHIEVEMENTS: &str = "sea_battle.achievements";
+// HOT_STREAK_LENGTH: The number of hits in a row for the "Hot streak" badge.
+const HOT_STREAK_LENGTH: usize = 5;
+// SHARPSHOOTER_ACCURACY: The share of hits in a won game for the "Sharpshooter" badge.
+const SHARPSHOOTER_ACCURACY: f64 = 0.5;
+
+/// Badges that human players can unlock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Achievement {
+    FirstHit,
+    HotStreak,
+    SurgicalStrike,
+    FlawlessVictory,
+    Sharpshooter,
+}
+
+impl Achievement {
+    pub fn all() -> Vec<Achievement> {
+        vec![
+            Achievement::FirstHit,
+            Achievement::HotStreak,
+            Achievement::SurgicalStrike,
+            Achievement::FlawlessVictory,
+            Achievement::Sharpshooter,
+        ]
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            Achievement::FirstHit => tr("achievement.first_hit"),
+            Achievement::HotStreak => tr("achievement.hot_streak"),
+            Achievement::SurgicalStrike => tr("achievement.surgical_strike"),
+            Achievement::FlawlessVictory => tr("achievement.flawless_victory"),
+            Achievement::Sharpshooter => tr("achievement.sharpshooter"),
+        }
+    }
+
+    pub fn get_description(&self) -> String {
+        match self {
+            Achievement::FirstHit => tr("achievement.first_hit_description"),
+            Achievement::HotStreak => tr_count("achievement.hot_streak_description", HOT_STREAK_LENGTH),
+            Achievement::SurgicalStrike => tr("achievement.surgical_strike_description"),
+            Achievement::FlawlessVictory => tr("achievement.flawless_victory_description"),
+            Achievement::Sharpshooter => {
+                let accuracy: String = format!("{:.0}", SHARPSHOOTER_ACCURACY * 100.0);
+                trf("achievement.sharpshooter_description", &[accuracy.as_str()])
+            }
+        }
+    }
+}
+
+/// An achievement unlocked by a profile.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnlockedAchievement {
+    pub achievement: Achievement,
+    pub profile: String,
+    // date: The date of unlocking in the form "YYYY-MM-DD".
+    pub date: String,
+}
+
+/// Follows the events of the current game and finds the achievements earned in it.
+#[derive(Default)]
+pub struct AchievementTracker {
+    // players: The name of each player and "True" if he is controlled manually.
+    players: Vec<(String, bool)>,
+    num_shots: Vec<usize>,
+    num_hits: Vec<usize>,
+    num_ships_lost: Vec<usize>,
+}
+
+impl AchievementTracker {
+    /// Takes the event into account.
+    /// Returns the achievements earned by it with the names of the profiles of the human players.
+    pub fn handle_event(&mut self, event: &GameEvent) -> Vec<(Achievement, String)> {
+        let mut earned: Vec<(Achievement, usize)> = Vec::new();
+        match event {
+            GameEvent::GameStarted { players } => {
+                self.players = players.clone();
+                self.num_shots = vec![0; players.len()];
+                self.num_hits = vec![0; players.len()];
+                self.num_ships_lost = vec![0; players.len()];
+            }
+            GameEvent::ShotFired { idx_player, is_hit, .. } => {
+                if let Some(num_shots) = self.num_shots.get_mut(*idx_player) {
+                    *num_shots += 1;
+                }
+                if *is_hit {
+                    if let Some(num_hits) = self.num_hits.get_mut(*idx_player) {
+                        *num_hits += 1;
+                    }
+                    earned.push((Achievement::FirstHit, *idx_player));
+                }
+            }
+            GameEvent::ShipSunk { idx_player, idx_target, size, num_shots } => {
+                if let Some(num_ships_lost) = self.num_ships_lost.get_mut(*idx_target) {
+                    *num_ships_lost += 1;
+                }
+                if *size == 4 && *num_shots <= 4 {
+                    earned.push((Achievement::SurgicalStrike, *idx_player));
+                }
+            }
+            GameEvent::HitStreak { idx_player, length } => {
+                if *length >= HOT_STREAK_LENGTH {
+                    earned.push((Achievement::HotStreak, *idx_player));
+                }
+            }
+            GameEvent::GameWon { winners } => {
+                for &idx_winner in winners.iter() {
+                    if self.num_ships_lost.get(idx_winner) == Some(&0) {
+                        earned.push((Achievement::FlawlessVictory, idx_winner));
+                    }
+                    let num_shots: usize = self.num_shots.get(idx_winner).cloned().unwrap_or(0);
+                    let num_hits: usize = self.num_hits.get(idx_winner).cloned().unwrap_or(0);
+                    if num_shots > 0 && num_hits as f64 >= num_shots as f64 * SHARPSHOOTER_ACCURACY {
+                        earned.push((Achievement::Sharpshooter, idx_winner));
+                    }
+                }
+            }
+        }
+        // Only human players earn achievements.
+        earned.into_iter()
+            .filter_map(|(achievement, idx_player)| match self.players.get(idx_player) {
+                Some((name, true)) => Some((achievement, name.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Restores the unlocked achievements from the local storage.
+pub fn load_unlocked() -> Vec<UnlockedAchievement> {
+    storage::load(KEY_ACHIEVEMENTS)
+}
+
+/// Saves the achievement of the profile if it was not unlocked yet.
+/// Returns "True" if it is new.
+pub fn unlock(achievement: Achievement, profile: &str, date: &str) -> bool {
+    let mut unlocked: Vec<UnlockedAchievement> = load_unlocked();
+    if unlocked.iter().any(|other| other.achievement == achievement && other.profile == profile) {
+        return false;
+    }
+    unlocked.push(UnlockedAchievement {
+        achievement,
+        profile: profile.to_string(),
+        date: date.to_string(),
+    });
+    storage::save(KEY_ACHIEVEMENTS, &unlocked);
+    true
+}
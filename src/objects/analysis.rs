@@ -0,0 +1,191 @@
+use crate::objects::game::{Game, ShotRecord, ShotResult};
+
+/// The statistics of the shots of a player after the game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayerStats {
+    pub num_shots: usize,
+    pub num_hits: usize,
+    // shots_to_first_hit: How many shots the player made up to and including the first hit.
+    pub shots_to_first_hit: Option<usize>,
+    // shots_to_sink: For each size of the sunk ships, the average number of shots of the player
+    // from the first hit on the ship to its sinking.
+    pub shots_to_sink: Vec<(usize, f64)>,
+    pub longest_miss_streak: usize,
+}
+
+impl PlayerStats {
+    /// Returns the share of hits among the shots from 0 to 1.
+    pub fn get_accuracy(&self) -> f64 {
+        if self.num_shots == 0 {
+            return 0.0;
+        }
+        self.num_hits as f64 / self.num_shots as f64
+    }
+}
+
+/// Calculates the statistics of the shots of the player with the index.
+pub fn get_player_stats(game: &Game, idx_player: usize) -> PlayerStats {
+    let shots: &Vec<ShotRecord> = game.get_shots();
+    let own_shots: Vec<&ShotRecord> = shots.iter().filter(|shot| shot.idx_player == idx_player).collect();
+    let num_hits: usize = own_shots.iter().filter(|shot| shot.result != ShotResult::Miss).count();
+    let shots_to_first_hit: Option<usize> = own_shots.iter()
+        .position(|shot| shot.result != ShotResult::Miss)
+        .map(|idx| idx + 1);
+    let mut longest_miss_streak: usize = 0;
+    let mut miss_streak: usize = 0;
+    for shot in own_shots.iter() {
+        if shot.result == ShotResult::Miss {
+            miss_streak += 1;
+            longest_miss_streak = longest_miss_streak.max(miss_streak);
+        } else {
+            miss_streak = 0;
+        }
+    }
+    // (size of the ship, number of shots) for each ship sunk by the player.
+    let mut sunk_ships: Vec<(usize, usize)> = Vec::new();
+    for (idx_sinking, sinking) in shots.iter().enumerate() {
+        let idx_ship: usize = match (sinking.idx_player == idx_player, sinking.result) {
+            (true, ShotResult::Sunk(idx_ship)) => { idx_ship }
+            _ => { continue; }
+        };
+        let num_shots: usize = count_shots_to_sink(shots, idx_sinking);
+        let size: usize = game.get_player(sinking.idx_target)
+            .and_then(|target| target.get_ships_as_iter().nth(idx_ship))
+            .map_or(0, |ship| ship.get_size());
+        sunk_ships.push((size, num_shots));
+    }
+    let mut sizes: Vec<usize> = sunk_ships.iter().map(|&(size, _)| size).collect();
+    sizes.sort_unstable();
+    sizes.dedup();
+    let shots_to_sink: Vec<(usize, f64)> = sizes.iter()
+        .map(|&size| {
+            let counts: Vec<usize> = sunk_ships.iter()
+                .filter(|&&(s, _)| s == size)
+                .map(|&(_, num_shots)| num_shots)
+                .collect();
+            (size, counts.iter().sum::<usize>() as f64 / counts.len() as f64)
+        })
+        .collect();
+    PlayerStats {
+        num_shots: own_shots.len(),
+        num_hits,
+        shots_to_first_hit,
+        shots_to_sink,
+        longest_miss_streak,
+    }
+}
+
+/// Returns the number of shots of the player who sank the ship with the shot "idx_sinking"
+/// at the same field, from the first hit on the ship to its sinking.
+pub fn count_shots_to_sink(shots: &[ShotRecord], idx_sinking: usize) -> usize {
+    let sinking: &ShotRecord = match shots.get(idx_sinking) {
+        None => { return 0; }
+        Some(shot) => { shot }
+    };
+    let idx_ship: Option<usize> = sinking.idx_ship;
+    let idx_first_hit: usize = shots.iter()
+        .position(|shot| shot.idx_target == sinking.idx_target && shot.idx_ship.is_some() && shot.idx_ship == idx_ship)
+        .unwrap_or(idx_sinking);
+    shots[idx_first_hit..=idx_sinking].iter()
+        .filter(|shot| shot.idx_player == sinking.idx_player && shot.idx_target == sinking.idx_target)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::player::Player;
+    use crate::objects::shape::ShapeKind;
+
+    fn create_shot(idx_player: usize, idx_target: usize, idx_ship: Option<usize>, result: ShotResult) -> ShotRecord {
+        ShotRecord { idx_player, idx_target, idx_cell: 0, idx_ship, result }
+    }
+
+    /// Returns a cell of the field of the target without a deck that was not shot yet.
+    fn find_water(game: &Game, idx_target: usize) -> usize {
+        let target: &Player = game.get_player(idx_target).unwrap();
+        (0..36)
+            .find(|&idx| target.get_ship_idx_by_cell(idx).is_none() && !target.is_cell_shot(idx))
+            .unwrap()
+    }
+
+    #[test]
+    fn counts_shots_from_first_hit_to_sinking() {
+        let shots: Vec<ShotRecord> = vec![
+            create_shot(0, 1, Some(0), ShotResult::Hit),
+            create_shot(0, 1, None, ShotResult::Miss),
+            // The shots of other players and at other fields are not counted.
+            create_shot(1, 0, None, ShotResult::Miss),
+            create_shot(0, 2, None, ShotResult::Miss),
+            create_shot(0, 1, Some(0), ShotResult::Sunk(0)),
+        ];
+        assert_eq!(count_shots_to_sink(&shots, 4), 3);
+        assert_eq!(count_shots_to_sink(&shots, 5), 0);
+    }
+
+    #[test]
+    fn counts_one_shot_for_ship_sunk_at_once() {
+        let shots: Vec<ShotRecord> = vec![
+            // The ship with the same index on another field does not start the count.
+            create_shot(0, 2, Some(1), ShotResult::Hit),
+            create_shot(0, 1, Some(1), ShotResult::Sunk(1)),
+        ];
+        assert_eq!(count_shots_to_sink(&shots, 1), 1);
+    }
+
+    #[test]
+    fn counts_from_first_hit_of_any_player() {
+        let shots: Vec<ShotRecord> = vec![
+            create_shot(2, 1, Some(0), ShotResult::Hit),
+            create_shot(2, 1, None, ShotResult::Miss),
+            create_shot(0, 1, Some(0), ShotResult::Sunk(0)),
+        ];
+        assert_eq!(count_shots_to_sink(&shots, 2), 1);
+    }
+
+    #[test]
+    fn sums_up_shots_of_each_player() {
+        let players: Vec<Player> = (0..2)
+            .map(|idx| Player::create(
+                "Player", false, 6, 6, &[ShapeKind::Straight(2), ShapeKind::Straight(1)], Some(idx),
+            ))
+            .collect();
+        let mut game = Game::create(players, &[None, None], false);
+        let decks: Vec<Vec<usize>> = game.get_player(1).unwrap().get_ships_as_iter()
+            .map(|ship| ship.get_area_ship())
+            .collect();
+        // The target of each shot and the deck to hit, or None for a miss.
+        let shots: Vec<(usize, Option<usize>)> = vec![
+            (1, None),
+            (0, None),
+            (1, Some(decks[0][0])),
+            (1, None),
+            (0, None),
+            (1, Some(decks[0][1])),
+            (1, Some(decks[1][0])),
+        ];
+        for (idx_target, idx_deck) in shots {
+            let idx_cell: usize = idx_deck.unwrap_or_else(|| find_water(&game, idx_target));
+            assert!(game.shoot(idx_target, idx_cell).is_some());
+        }
+        assert!(game.is_over());
+        let stats: PlayerStats = get_player_stats(&game, 0);
+        assert_eq!(stats, PlayerStats {
+            num_shots: 5,
+            num_hits: 3,
+            shots_to_first_hit: Some(2),
+            shots_to_sink: vec![(1, 1.0), (2, 3.0)],
+            longest_miss_streak: 1,
+        });
+        assert!((stats.get_accuracy() - 0.6).abs() < 1e-9);
+        let stats: PlayerStats = get_player_stats(&game, 1);
+        assert_eq!(stats, PlayerStats {
+            num_shots: 2,
+            num_hits: 0,
+            shots_to_first_hit: None,
+            shots_to_sink: Vec::new(),
+            longest_miss_streak: 2,
+        });
+        assert_eq!(stats.get_accuracy(), 0.0);
+    }
+}
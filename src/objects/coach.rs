@@ -0,0 +1,78 @@
+// GOOD_SHOT_RATIO, INACCURACY_RATIO: The shot is graded by the ratio of the probability of the chosen cell
+// to the probability of the best cell.
+const GOOD_SHOT_RATIO: f64 = 0.9;
+const INACCURACY_RATIO: f64 = 0.5;
+
+/// The grade of a shot compared with the best shot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Grade {
+    Good,
+    Inaccuracy,
+    Blunder,
+}
+
+impl Grade {
+    /// Returns the name of the grade, by which its label is translated.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Grade::Good => "good",
+            Grade::Inaccuracy => "inaccuracy",
+            Grade::Blunder => "blunder",
+        }
+    }
+
+    /// Returns the CSS class of the rated shot.
+    pub fn get_class(&self) -> &'static str {
+        match self {
+            Grade::Good => "coach-good",
+            Grade::Inaccuracy => "coach-inaccuracy",
+            Grade::Blunder => "coach-blunder",
+        }
+    }
+}
+
+/// The rating of a shot of a human player.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShotRating {
+    pub idx_player: usize,
+    pub idx_target: usize,
+    pub idx_cell: usize,
+    // probability: The probability that the chosen cell contains a deck.
+    pub probability: f64,
+    // idx_best_cell, best_probability: The most probable cell and its probability.
+    pub idx_best_cell: usize,
+    pub best_probability: f64,
+    pub grade: Grade,
+}
+
+impl ShotRating {
+    /// Returns how much probability of a hit was lost compared with the best shot.
+    pub fn get_loss(&self) -> f64 {
+        self.best_probability - self.probability
+    }
+}
+
+/// Rates the shot at the cell by the probability map of the target field made before the shot.
+pub fn rate_shot(idx_player: usize, idx_target: usize, idx_cell: usize, probability_map: &[f64]) -> ShotRating {
+    let probability: f64 = probability_map.get(idx_cell).cloned().unwrap_or(0.0);
+    let (idx_best_cell, best_probability) = probability_map.iter().cloned().enumerate()
+        .fold((idx_cell, probability), |best, (idx, p)| if p > best.1 { (idx, p) } else { best });
+    let grade = if best_probability <= 0.0 || probability >= best_probability * GOOD_SHOT_RATIO {
+        Grade::Good
+    } else if probability >= best_probability * INACCURACY_RATIO {
+        Grade::Inaccuracy
+    } else {
+        Grade::Blunder
+    };
+    ShotRating { idx_player, idx_target, idx_cell, probability, idx_best_cell, best_probability, grade }
+}
+
+/// Returns the worst shots of the player, the biggest loss first.
+pub fn get_biggest_mistakes(ratings: &[ShotRating], idx_player: usize, num_mistakes: usize) -> Vec<&ShotRating> {
+    let mut mistakes: Vec<&ShotRating> = ratings.iter()
+        .filter(|rating| rating.idx_player == idx_player && rating.grade != Grade::Good)
+        .collect();
+    mistakes.sort_by(|a, b| b.get_loss().partial_cmp(&a.get_loss()).unwrap_or(std::cmp::Ordering::Equal));
+    mistakes.truncate(num_mistakes);
+    mistakes
+}
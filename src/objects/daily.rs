@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use crate::objects::shape::{ShapeKind, get_classic_fleet};
+
+// WIDTH_FIELD, HEIGHT_FIELD: The daily challenge is always played on the classic field,
+// so that everyone gets the same board regardless of their settings.
+pub const WIDTH_FIELD: usize = 10;
+pub const HEIGHT_FIELD: usize = 10;
+
+/// The result of the daily challenge.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DailyResult {
+    // date: The date of the challenge in the form "YYYY-MM-DD".
+    pub date: String,
+    pub shots: usize,
+    pub par: usize,
+    pub seconds: u64,
+    // shots_pattern: For each shot in order, "True" if it hit a deck.
+    pub shots_pattern: Vec<bool>,
+}
+
+/// Returns the fleet of the daily challenge.
+pub fn get_daily_fleet() -> Vec<ShapeKind> {
+    get_classic_fleet()
+}
+
+/// Returns the current local date in the form "YYYY-MM-DD".
+pub fn get_today() -> String {
+    let date = js_sys::Date::new_0();
+    format!("{:04}-{:02}-{:02}", date.get_full_year(), date.get_month() + 1, date.get_date())
+}
+
+/// Returns the seed of the layout of the daily challenge for the date.
+/// The same date always gives the same seed (64-bit FNV-1a hash of the text).
+pub fn get_daily_seed(date: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in date.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
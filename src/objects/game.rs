@@ -0,0 +1,509 @@
+use crate::objects::player::{Player, AiLevel};
+use crate::objects::ship::Maneuver;
+use rand::Rng;
+use std::collections::HashMap;
+
+/// The result of a shot at a cell.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShotResult {
+    Miss,
+    Hit,
+    // Sunk: The shot sank the ship with the given index.
+    Sunk(usize),
+}
+
+/// A mark that a player puts on a cell of the opponent's field without shooting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Annotation {
+    // Water: The player is sure that there is no deck on the cell.
+    Water,
+    // Ship: The player suspects that there is a deck on the cell.
+    Ship,
+}
+
+/// Everything that changed on the target field after a shot.
+pub struct ShotOutcome {
+    pub result: ShotResult,
+    // area_near_ship: Cells near the sunk ship, in which there can be no other ships.
+    pub area_near_ship: Vec<usize>,
+}
+
+/// A shot made during the game.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShotRecord {
+    // idx_player: The index of the player who shot.
+    pub idx_player: usize,
+    pub idx_target: usize,
+    pub idx_cell: usize,
+    // idx_ship: The index of the ship of the target that was hit, or None after a miss.
+    pub idx_ship: Option<usize>,
+    pub result: ShotResult,
+}
+
+/// Everything that changed on the field of the player after the maneuver of his ship.
+pub struct ManeuverOutcome {
+    pub old_area: Vec<usize>,
+    pub new_area: Vec<usize>,
+    // stale_cells: Cells with misses that the opponents can no longer trust.
+    pub stale_cells: Vec<usize>,
+}
+
+/// The move chosen by a computer player.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AutoMove {
+    // Shot: The index of the target player and the index of the cell.
+    Shot(usize, usize),
+    // Maneuver: The index of the own ship and the maneuver.
+    Maneuver(usize, Maneuver),
+}
+
+/// The rules of the game for any number of players, independent of the UI.
+pub struct Game {
+    players: Vec<Player>,
+    // sides: For each player, the identifier of the side he plays for.
+    // Allies have the same side, a player without a team has his own side.
+    sides: Vec<usize>,
+    // turn_order: The order in which players move, the sides alternate.
+    turn_order: Vec<usize>,
+    // whose_move: The index of the player whose turn it is.
+    whose_move: usize,
+    // last_targets: For each player, the index of the player he shot at last.
+    last_targets: Vec<Option<usize>>,
+    is_moving_fleet: bool,
+    // shots: All shots of the game in order.
+    shots: Vec<ShotRecord>,
+    // annotations: For each field, the marks put on its cells that were not shot yet.
+    annotations: Vec<HashMap<usize, Annotation>>,
+}
+
+impl Game {
+    /// Creates a game, "teams" contains the team of each player or None if he plays alone.
+    pub fn create(players: Vec<Player>, teams: &[Option<usize>], is_moving_fleet: bool) -> Self {
+        let num_players = players.len();
+        let sides: Vec<usize> = (0..num_players)
+            .map(|idx| match teams.get(idx).cloned().flatten() {
+                Some(team) => team,
+                // Players without a team get sides that do not match any team.
+                None => num_players + idx,
+            })
+            .collect();
+        // Players of each side in their order, then take one player from each side in turn.
+        let mut queues: Vec<Vec<usize>> = Vec::new();
+        let mut queue_sides: Vec<usize> = Vec::new();
+        for (idx, &side) in sides.iter().enumerate() {
+            match queue_sides.iter().position(|&s| s == side) {
+                Some(idx_queue) => queues[idx_queue].push(idx),
+                None => {
+                    queue_sides.push(side);
+                    queues.push(vec![idx]);
+                }
+            }
+        }
+        let mut turn_order: Vec<usize> = Vec::new();
+        for round in 0..num_players {
+            for queue in queues.iter() {
+                if let Some(&idx) = queue.get(round) {
+                    turn_order.push(idx);
+                }
+            }
+        }
+        let whose_move = turn_order.first().cloned().unwrap_or(0);
+        Self {
+            players,
+            sides,
+            turn_order,
+            whose_move,
+            last_targets: vec![None; num_players],
+            is_moving_fleet,
+            shots: Vec::new(),
+            annotations: vec![HashMap::new(); num_players],
+        }
+    }
+
+    pub fn get_players_as_iter(&self) -> std::slice::Iter<'_, Player> {
+        self.players.iter()
+    }
+
+    pub fn get_player(&self, idx_player: usize) -> Option<&Player> {
+        self.players.get(idx_player)
+    }
+
+    pub fn get_num_players(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn get_annotation(&self, idx_target: usize, idx_cell: usize) -> Option<Annotation> {
+        self.annotations.get(idx_target)?.get(&idx_cell).cloned()
+    }
+
+    /// Changes the mark of the cell that was not shot: none, water, ship.
+    /// Returns "False" if the cell cannot be marked.
+    pub fn annotate(&mut self, idx_target: usize, idx_cell: usize) -> bool {
+        if self.is_over() || self.players.get(idx_target).map_or(true, |target| target.is_cell_shot(idx_cell)) {
+            return false;
+        }
+        let annotations: &mut HashMap<usize, Annotation> = &mut self.annotations[idx_target];
+        match annotations.get(&idx_cell) {
+            None => { annotations.insert(idx_cell, Annotation::Water); }
+            Some(Annotation::Water) => { annotations.insert(idx_cell, Annotation::Ship); }
+            Some(Annotation::Ship) => { annotations.remove(&idx_cell); }
+        }
+        true
+    }
+
+    pub fn get_shots(&self) -> &Vec<ShotRecord> {
+        self.shots.as_ref()
+    }
+
+    /// Returns how many times in a row the player has hit with his last shots.
+    pub fn get_hit_streak(&self, idx_player: usize) -> usize {
+        self.shots.iter().rev()
+            .filter(|shot| shot.idx_player == idx_player)
+            .take_while(|shot| shot.result != ShotResult::Miss)
+            .count()
+    }
+
+    pub fn get_whose_move(&self) -> usize {
+        self.whose_move
+    }
+
+    /// Returns "True" if the player still has living ships.
+    pub fn is_player_alive(&self, idx_player: usize) -> bool {
+        self.players.get(idx_player)
+            .map_or(false, |player| player.get_num_living_ships() > 0)
+    }
+
+    /// Returns "True" if the players are opponents of each other.
+    pub fn is_opponent(&self, idx_player: usize, idx_other: usize) -> bool {
+        self.sides.get(idx_player) != self.sides.get(idx_other)
+    }
+
+    /// Returns the indexes of the allies of the player, not including himself.
+    pub fn get_allies(&self, idx_player: usize) -> Vec<usize> {
+        (0..self.players.len())
+            .filter(|&idx| idx != idx_player && !self.is_opponent(idx_player, idx))
+            .collect()
+    }
+
+    /// Returns the indexes of the opponents of the player that are still in the game.
+    pub fn get_alive_opponents(&self, idx_player: usize) -> Vec<usize> {
+        (0..self.players.len())
+            .filter(|&idx| self.is_opponent(idx_player, idx) && self.is_player_alive(idx))
+            .collect()
+    }
+
+    /// Returns the index of the player whom the player shot at last.
+    pub fn get_last_target(&self, idx_player: usize) -> Option<usize> {
+        self.last_targets.get(idx_player).cloned().flatten()
+    }
+
+    /// Returns the indexes of the winners (all players of the winning side),
+    /// if only one side is left in the game, otherwise an empty vector.
+    pub fn get_winners(&self) -> Vec<usize> {
+        let alive_players: Vec<usize> = (0..self.players.len())
+            .filter(|&idx| self.is_player_alive(idx))
+            .collect();
+        match alive_players.first() {
+            Some(&idx_alive) if self.get_alive_opponents(idx_alive).is_empty() => {
+                let mut winners: Vec<usize> = self.get_allies(idx_alive);
+                winners.push(idx_alive);
+                winners.sort();
+                winners
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        !self.get_winners().is_empty()
+    }
+
+    /// Transfers the move to the next player who is still in the game.
+    fn pass_turn(&mut self) {
+        let num_turns = self.turn_order.len();
+        let position = self.turn_order.iter().position(|&idx| idx == self.whose_move).unwrap_or(0);
+        for step in 1..=num_turns {
+            let idx_next = self.turn_order[(position + step) % num_turns];
+            if self.is_player_alive(idx_next) {
+                self.whose_move = idx_next;
+                return;
+            }
+        }
+    }
+
+    /// The player whose turn it is shoots at the cell of the target player.
+    /// Returns None if the shot is not allowed.
+    /// After a miss, the turn passes to the next player.
+    pub fn shoot(&mut self, idx_target: usize, idx_cell: usize) -> Option<ShotOutcome> {
+        if self.is_over()
+            || !self.is_opponent(self.whose_move, idx_target)
+            || !self.is_player_alive(idx_target) {
+            return None;
+        }
+        let target: &mut Player = &mut self.players[idx_target];
+        // The cell has already been shot, the result is known.
+        if target.is_cell_shot(idx_cell) {
+            return None;
+        }
+        let (number_ship, is_successful_shot, is_alive_ship) = target.process_a_shot(idx_cell);
+        // The result of the shot replaces the guess about the cell.
+        self.annotations[idx_target].remove(&idx_cell);
+        self.last_targets[self.whose_move] = Some(idx_target);
+        let mut area_near_ship: Vec<usize> = Vec::new();
+        let result: ShotResult = match (number_ship, is_successful_shot, is_alive_ship) {
+            (Some(idx_ship), true, false) => {
+                if let Some(ship) = target.get_ship_by_idx_as_mut_ref(idx_ship) {
+                    area_near_ship = ship.get_area_near_ship(None);
+                }
+                ShotResult::Sunk(idx_ship)
+            }
+            (_, true, _) => ShotResult::Hit,
+            _ => ShotResult::Miss,
+        };
+        self.shots.push(ShotRecord {
+            idx_player: self.whose_move,
+            idx_target,
+            idx_cell,
+            idx_ship: if is_successful_shot { number_ship } else { None },
+            result,
+        });
+        if result == ShotResult::Miss {
+            self.pass_turn();
+        }
+        Some(ShotOutcome {
+            result,
+            area_near_ship,
+        })
+    }
+
+    /// The player whose turn it is moves his ship instead of a shot.
+    /// Returns None if the maneuver is not allowed.
+    pub fn maneuver(&mut self, idx_ship: usize, maneuver: Maneuver) -> Option<ManeuverOutcome> {
+        if !self.is_moving_fleet || self.is_over() {
+            return None;
+        }
+        let player: &mut Player = &mut self.players[self.whose_move];
+        let (old_area, new_area) = player.maneuver_ship(idx_ship, maneuver)?;
+        // The opponents can no longer trust their misses.
+        let stale_cells = player.invalidate_misses();
+        self.pass_turn();
+        Some(ManeuverOutcome {
+            old_area,
+            new_area,
+            stale_cells,
+        })
+    }
+
+    /// Chooses the move of the player whose turn it is, as the computer would do it.
+    /// The target is the opponent with the cell most likely containing a deck,
+    /// among equal ones the computer supports the allies and fires at their last target.
+    pub fn generate_auto_move(&self) -> Option<AutoMove> {
+        if self.is_over() {
+            return None;
+        }
+        let mut rng = rand::thread_rng();
+        if self.is_moving_fleet && rng.gen_range(0, 5) == 0 {
+            if let Some((idx_ship, maneuver)) = self.players[self.whose_move].generate_new_auto_maneuver() {
+                return Some(AutoMove::Maneuver(idx_ship, maneuver));
+            }
+        }
+        let ai_level: AiLevel = self.players[self.whose_move].get_ai_level();
+        let mut best_moves: Vec<(usize, usize)> = Vec::new();
+        let mut max_probability: f64 = -1.0;
+        for idx_target in self.get_alive_opponents(self.whose_move) {
+            let target: &Player = &self.players[idx_target];
+            let (idx_cell, probability) = match target.generate_new_auto_shot_for_level(ai_level, &mut rng) {
+                None => { continue; }
+                Some(shot) => { shot }
+            };
+            if probability > max_probability + f64::EPSILON {
+                max_probability = probability;
+                best_moves.clear();
+            }
+            if probability >= max_probability - f64::EPSILON {
+                best_moves.push((idx_target, idx_cell));
+            }
+        }
+        if best_moves.is_empty() {
+            return None;
+        }
+        let ally_targets: Vec<usize> = self.get_allies(self.whose_move).iter()
+            .filter_map(|&idx_ally| self.last_targets[idx_ally])
+            .collect();
+        let focused_moves: Vec<(usize, usize)> = best_moves.iter()
+            .filter(|(idx_target, _)| ally_targets.contains(idx_target))
+            .cloned()
+            .collect();
+        if !focused_moves.is_empty() {
+            best_moves = focused_moves;
+        }
+        let (idx_target, idx_cell) = best_moves[rng.gen_range(0, best_moves.len())];
+        Some(AutoMove::Shot(idx_target, idx_cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::shape::ShapeKind;
+
+    // WIDTH_FIELD, HEIGHT_FIELD: The size of the fields in the tests.
+    const WIDTH_FIELD: usize = 6;
+    const HEIGHT_FIELD: usize = 6;
+
+    /// Creates a game of the players with the teams, each fleet is placed by its own seed.
+    fn create_game(teams: &[Option<usize>]) -> Game {
+        let players: Vec<Player> = (0..teams.len())
+            .map(|idx| Player::create(
+                format!("Player {}", idx).as_str(),
+                false,
+                WIDTH_FIELD,
+                HEIGHT_FIELD,
+                &[ShapeKind::Straight(2), ShapeKind::Straight(1)],
+                Some(idx as u64),
+            ))
+            .collect();
+        Game::create(players, teams, false)
+    }
+
+    /// Returns a cell of the field of the target without a deck that was not shot yet.
+    fn find_water(game: &Game, idx_target: usize) -> usize {
+        let target: &Player = game.get_player(idx_target).unwrap();
+        (0..WIDTH_FIELD * HEIGHT_FIELD)
+            .find(|&idx| target.get_ship_idx_by_cell(idx).is_none() && !target.is_cell_shot(idx))
+            .unwrap()
+    }
+
+    /// The player whose turn it is misses at the field of the target.
+    fn miss(game: &mut Game, idx_target: usize) {
+        let idx_cell: usize = find_water(game, idx_target);
+        assert_eq!(game.shoot(idx_target, idx_cell).unwrap().result, ShotResult::Miss);
+    }
+
+    /// The player whose turn it is sinks the whole fleet of the target.
+    fn sink_fleet(game: &mut Game, idx_target: usize) {
+        let decks: Vec<usize> = game.get_player(idx_target).unwrap().get_ships_as_iter()
+            .flat_map(|ship| ship.get_area_ship())
+            .collect();
+        for idx_cell in decks {
+            assert_ne!(game.shoot(idx_target, idx_cell).unwrap().result, ShotResult::Miss);
+        }
+    }
+
+    #[test]
+    fn passes_turn_in_order_after_miss() {
+        let mut game = create_game(&[None, None, None]);
+        assert_eq!(game.get_whose_move(), 0);
+        miss(&mut game, 2);
+        assert_eq!(game.get_whose_move(), 1);
+        miss(&mut game, 0);
+        assert_eq!(game.get_whose_move(), 2);
+        miss(&mut game, 1);
+        assert_eq!(game.get_whose_move(), 0);
+    }
+
+    #[test]
+    fn keeps_turn_after_hit() {
+        let mut game = create_game(&[None, None, None]);
+        let idx_deck: usize = game.get_player(1).unwrap().get_ships_as_iter().next().unwrap().get_area_ship()[0];
+        assert_eq!(game.shoot(1, idx_deck).unwrap().result, ShotResult::Hit);
+        assert_eq!(game.get_whose_move(), 0);
+        // The same cell cannot be shot twice, nor the own field.
+        assert!(game.shoot(1, idx_deck).is_none());
+        assert!(game.shoot(0, find_water(&game, 0)).is_none());
+    }
+
+    #[test]
+    fn skips_eliminated_players() {
+        let mut game = create_game(&[None, None, None]);
+        sink_fleet(&mut game, 1);
+        assert!(!game.is_player_alive(1));
+        assert!(!game.is_over());
+        assert_eq!(game.get_alive_opponents(0), vec![2]);
+        // The eliminated player cannot be shot at and does not move any more.
+        assert!(game.shoot(1, find_water(&game, 1)).is_none());
+        miss(&mut game, 2);
+        assert_eq!(game.get_whose_move(), 2);
+        miss(&mut game, 0);
+        assert_eq!(game.get_whose_move(), 0);
+    }
+
+    #[test]
+    fn finds_winner_of_free_for_all() {
+        let mut game = create_game(&[None, None, None, None]);
+        assert!(game.get_winners().is_empty());
+        sink_fleet(&mut game, 1);
+        sink_fleet(&mut game, 2);
+        assert!(game.get_winners().is_empty());
+        sink_fleet(&mut game, 3);
+        assert_eq!(game.get_winners(), vec![0]);
+        assert!(game.is_over());
+        // Nothing can be done after the game is over.
+        assert!(game.shoot(1, find_water(&game, 1)).is_none());
+        assert!(game.generate_auto_move().is_none());
+    }
+
+    #[test]
+    fn alternates_teams_in_turn_order() {
+        let mut game = create_game(&[Some(0), Some(0), Some(1), Some(1)]);
+        let mut order: Vec<usize> = Vec::new();
+        for _ in 0..4 {
+            let idx_player: usize = game.get_whose_move();
+            order.push(idx_player);
+            let idx_target: usize = game.get_alive_opponents(idx_player)[0];
+            miss(&mut game, idx_target);
+        }
+        assert_eq!(order, vec![0, 2, 1, 3]);
+        assert_eq!(game.get_whose_move(), 0);
+    }
+
+    #[test]
+    fn shares_knowledge_between_allies() {
+        let mut game = create_game(&[Some(0), Some(0), Some(1), Some(1)]);
+        // The allies see each other's fleets and do not shoot at each other.
+        assert!(!game.is_opponent(0, 1));
+        assert!(game.is_opponent(0, 2));
+        assert_eq!(game.get_allies(0), vec![1]);
+        assert_eq!(game.get_alive_opponents(0), vec![2, 3]);
+        assert!(game.shoot(1, find_water(&game, 1)).is_none());
+    }
+
+    #[test]
+    fn supports_target_of_ally() {
+        let mut game = create_game(&[Some(0), Some(0), Some(1), Some(1)]);
+        // The same cell is water on both enemy fields, so after a miss at it on each field
+        // the computer knows the same about both of them.
+        let idx_cell: usize = (0..WIDTH_FIELD * HEIGHT_FIELD)
+            .find(|&idx| [2, 3].iter().all(|&idx_target| {
+                game.get_player(idx_target).unwrap().get_ship_idx_by_cell(idx).is_none()
+            }))
+            .unwrap();
+        assert_eq!(game.shoot(3, idx_cell).unwrap().result, ShotResult::Miss);
+        miss(&mut game, 0);
+        assert_eq!(game.shoot(2, idx_cell).unwrap().result, ShotResult::Miss);
+        miss(&mut game, 0);
+        // The first player last shot at the fourth one, but the ally last shot at the third one.
+        assert_eq!(game.get_whose_move(), 0);
+        for _ in 0..20 {
+            match game.generate_auto_move() {
+                Some(AutoMove::Shot(idx_target, _)) => { assert_eq!(idx_target, 2); }
+                auto_move => { panic!("unexpected move {:?}", auto_move); }
+            }
+        }
+    }
+
+    #[test]
+    fn gives_victory_to_whole_team() {
+        let mut game = create_game(&[Some(0), Some(0), Some(1), Some(1)]);
+        miss(&mut game, 2);
+        // The second team eliminates the first player.
+        sink_fleet(&mut game, 0);
+        assert!(!game.is_over());
+        miss(&mut game, 1);
+        // The second player alone wins for the team.
+        sink_fleet(&mut game, 2);
+        assert!(game.get_winners().is_empty());
+        sink_fleet(&mut game, 3);
+        assert_eq!(game.get_winners(), vec![0, 1]);
+    }
+}
@@ -1,2 +1,12 @@
 pub mod ship;
+pub mod shape;
 pub mod player;
+pub mod probability;
+pub mod game;
+pub mod daily;
+pub mod coach;
+pub mod analysis;
+pub mod profile;
+pub mod rating;
+pub mod puzzle;
+pub mod achievements;
@@ -1,24 +1,72 @@
-use crate::objects::ship::{Ship, get_default_fleet};
+use crate::objects::ship::{Ship, Maneuver, get_default_fleet};
+use crate::objects::shape::{Shape, ShapeKind};
+use crate::objects::probability::{FieldKnowledge, calculate_probability_map};
+use crate::i18n::tr;
 use std::slice::{Iter, IterMut};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+/// The skill of a computer player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AiLevel {
+    // Easy: Shoots at random cells that may still contain a deck.
+    Easy,
+    // Normal: Shoots at random until a hit, then finishes off the wounded ship.
+    Normal,
+    // Hard: Always shoots at the cell most likely to contain a deck.
+    Hard,
+}
+
+impl AiLevel {
+    pub fn all() -> Vec<AiLevel> {
+        vec![AiLevel::Easy, AiLevel::Normal, AiLevel::Hard]
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            AiLevel::Easy => tr("ai.easy"),
+            AiLevel::Normal => tr("ai.normal"),
+            AiLevel::Hard => tr("ai.hard"),
+        }
+    }
+}
 
 pub struct Player {
     name: String,
     ships: Vec<Ship>,
     misses_shots: Vec<usize>,
     fire_shots: Vec<usize>,
+    // stale_misses_shots: Misses made before one of the ships was moved.
+    // The cells may be occupied again, so they can be shot once more.
+    stale_misses_shots: Vec<usize>,
     _is_manual_control: bool,
+    // ai_level: The skill with which the computer shoots for this player.
+    ai_level: AiLevel,
+    width_field: usize,
+    height_field: usize,
 }
 
 
 impl Player {
-    pub fn create(name: &str, is_manual_control: bool) -> Self {
+    pub fn create(
+        name: &str,
+        is_manual_control: bool,
+        width_field: usize,
+        height_field: usize,
+        fleet: &[ShapeKind],
+        seed: Option<u64>,
+    ) -> Self {
         Self {
             name: name.to_string(),
-            ships: get_default_fleet(),
+            ships: get_default_fleet(width_field, height_field, fleet, seed),
             misses_shots: Vec::new(),
             fire_shots: Vec::new(),
+            stale_misses_shots: Vec::new(),
             _is_manual_control: is_manual_control,
+            ai_level: AiLevel::Hard,
+            width_field,
+            height_field,
         }
     }
     pub fn _set_name(&mut self, name: &str) {
@@ -27,6 +75,12 @@ impl Player {
     pub fn _set_type_control(&mut self, is_manual_control: bool) {
         self._is_manual_control = is_manual_control
     }
+    pub fn set_ai_level(&mut self, ai_level: AiLevel) {
+        self.ai_level = ai_level
+    }
+    pub fn get_ai_level(&self) -> AiLevel {
+        self.ai_level
+    }
     pub fn get_name(&self) -> &str {
         self.name.as_str()
     }
@@ -47,7 +101,7 @@ impl Player {
 
     // Returns a mutable reference to the ship by index, or None
     pub fn get_ship_by_idx_as_mut_ref(&mut self, idx_ship: usize) -> Option<&Ship> {
-        if idx_ship <= self.ships.len() {
+        if idx_ship < self.ships.len() {
             Some(&self.ships[idx_ship])
         } else {
             None
@@ -56,7 +110,7 @@ impl Player {
 
     // Returns a reference to the ship by index, or None
     pub fn _get_ship_by_idx_as_ref(&self, idx_ship: usize) -> Option<&Ship> {
-        if idx_ship <= self.ships.len() {
+        if idx_ship < self.ships.len() {
             Some(&self.ships[idx_ship])
         } else {
             None
@@ -77,109 +131,303 @@ impl Player {
                 number_ship = Some(idx_ship);
                 is_successful_shot = true;
                 is_alive_ship = ship.knock_down(idx_cell);
-                if !is_successful_shot {
-                    self.misses_shots.push(idx_ship)
-                } else {
-                    self.fire_shots.push(idx_ship)
-                };
                 break;
             }
         }
+        self.stale_misses_shots.retain(|&idx| idx != idx_cell);
+        if is_successful_shot {
+            self.fire_shots.push(idx_cell)
+        } else {
+            self.misses_shots.push(idx_cell)
+        };
 
         (number_ship, is_successful_shot, is_alive_ship)
     }
 
-    /// Own shot in your fleet
-    pub fn generate_new_auto_shot_idx(&self) -> usize {
-        // TODO This is a sketch for choosing a shooting location.
-        // TODO You need to come back later and rethink this code.
-        let mut rng = rand::thread_rng();
-        let mut variant_shot: Vec<usize> = Vec::new();
-        // Check if there are any damaged (but not killed) ships and make a shot next to them
-        for ship in self.get_ships_as_iter() {
-            if ship.check_is_wounded() {
-                // Take the indexes of the padded decks.
-                let fire_cell_idxes: &Vec<usize> = ship.get_fire_cell_idxes();
-                if fire_cell_idxes.len() == 1 {
-                    // If there is only one hit,
-                    // then we form options for shots in 4 directions
-                    variant_shot = self.calculate_near_area(fire_cell_idxes[0]);
-                } else if fire_cell_idxes.len() > 1 {
-                    // If there was more than one hit,
-                    // then we determine the direction of fire and form options for the shot.
-                    let is_horizontal: bool = fire_cell_idxes[0] % 10 == fire_cell_idxes[1] % 10;
-                    for (i, idx_cell) in fire_cell_idxes.iter().enumerate() {
-                        let calc_idx: usize = if is_horizontal { idx_cell % 10 } else { idx_cell / 10 };
-                        let direction_k: usize = if is_horizontal { 1 } else { 10 };
-                        if calc_idx != 0
-                            &&
-                            self.misses_shots.iter()
-                                .find(|&&idx| idx == idx_cell - 1 * direction_k).is_none()
-                            &&
-                            fire_cell_idxes.iter()
-                                .find(|&&idx| idx == idx_cell - 1 * direction_k).is_none() {
-                            variant_shot.push(idx_cell.clone());
-                        }
-                        if i == fire_cell_idxes.len() - 1
-                            &&
-                            calc_idx != 9
-                            &&
-                            self.misses_shots.iter()
-                                .find(|&&idx| idx == (idx_cell + 1 * direction_k)).is_none()
-                            &&
-                            fire_cell_idxes.iter()
-                                .find(|&&idx| idx == (idx_cell + 1 * direction_k)).is_none() {
-                            variant_shot.push(idx_cell.clone());
-                        }
-                    }
+    /// Returns "True" if the cell has already been shot
+    /// and the result of the shot is still relevant.
+    pub fn is_cell_shot(&self, idx_cell: usize) -> bool {
+        self.misses_shots.contains(&idx_cell) || self.fire_shots.contains(&idx_cell)
+    }
+
+    /// Returns the index of the ship located on the cell, or None
+    pub fn get_ship_idx_by_cell(&self, idx_cell: usize) -> Option<usize> {
+        self.ships.iter().position(|ship| ship.check_idx_for_ship(idx_cell))
+    }
+
+    /// Returns the index of the undamaged ship located on the cell, or None
+    pub fn get_undamaged_ship_idx_by_cell(&self, idx_cell: usize) -> Option<usize> {
+        self.ships.iter()
+            .position(|ship| ship.check_idx_for_ship(idx_cell) && ship.check_is_undamaged())
+    }
+
+    /// Returns the new location and position of the ship after the maneuver,
+    /// or None if the maneuver is not allowed.
+    /// Only undamaged ships can be moved, and the ship must not touch other ships.
+    fn check_maneuver(&self, idx_ship: usize, maneuver: Maneuver) -> Option<(usize, Shape)> {
+        let ship = self.ships.get(idx_ship)?;
+        if !ship.check_is_undamaged() {
+            return None;
+        }
+        let (idx_origin, shape) = ship.get_position_after_maneuver(maneuver)?;
+        let new_area = ship.get_area_for_position(idx_origin, &shape)?;
+        for (idx_other, other) in self.ships.iter().enumerate() {
+            if idx_other == idx_ship { continue; }
+            let mut occupied_area = other.get_area_ship();
+            occupied_area.extend(other.get_area_near_ship(None));
+            if new_area.iter().any(|idx| occupied_area.contains(idx)) {
+                return None;
+            }
+        }
+        Some((idx_origin, shape))
+    }
+
+    /// Moves or rotates the ship.
+    /// Returns the cells occupied by the ship before and after the maneuver,
+    /// or None if the maneuver is not allowed.
+    pub fn maneuver_ship(&mut self, idx_ship: usize, maneuver: Maneuver) -> Option<(Vec<usize>, Vec<usize>)> {
+        let (idx_origin, shape) = self.check_maneuver(idx_ship, maneuver)?;
+        let ship = &mut self.ships[idx_ship];
+        let old_area = ship.get_area_ship();
+        ship.set_location_and_position(idx_origin, shape);
+        Some((old_area, ship.get_area_ship()))
+    }
+
+    /// Marks the misses as stale, since after the maneuver
+    /// a ship may be in a cell where there was a miss.
+    /// The misses next to sunk ships stay known, no ship can move there.
+    /// Returns the indexes of the cells that are no longer known.
+    pub fn invalidate_misses(&mut self) -> Vec<usize> {
+        let area_near_sunk: Vec<usize> = self.ships.iter()
+            .filter(|ship| !ship.check_is_alive())
+            .flat_map(|ship| ship.get_area_near_ship(None))
+            .collect();
+        let (kept_misses, stale_misses): (Vec<usize>, Vec<usize>) = self.misses_shots.iter()
+            .partition(|idx| area_near_sunk.contains(idx));
+        self.misses_shots = kept_misses;
+        self.stale_misses_shots.extend(stale_misses.iter());
+        stale_misses
+    }
+
+    /// Randomly choose an allowed maneuver of one of the undamaged ships, or None
+    pub fn generate_new_auto_maneuver(&self) -> Option<(usize, Maneuver)> {
+        let maneuvers = [Maneuver::Up, Maneuver::Down, Maneuver::Left, Maneuver::Right, Maneuver::Rotate];
+        let mut variants: Vec<(usize, Maneuver)> = Vec::new();
+        for idx_ship in 0..self.ships.len() {
+            for &maneuver in maneuvers.iter() {
+                if self.check_maneuver(idx_ship, maneuver).is_some() {
+                    variants.push((idx_ship, maneuver));
                 }
-                // There is enough information about one ship.
-                break;
             }
         }
-        let result_val: usize = if variant_shot.len() != 0 {
-            variant_shot[rng.gen_range(0, variant_shot.len())]
+        if variants.is_empty() {
+            None
         } else {
-            let mut count: usize = 0;
-            let mut tmp_idx: usize = 0;
-            loop {
-                tmp_idx = rng.gen_range(0, 100);
-                // Check if the generated index is missing from the miss and fire list
-                if self.misses_shots.iter().find(|&&idx| idx == tmp_idx).is_none() {
-                    if self.fire_shots.iter().find(|&&idx| idx == tmp_idx).is_none() {
-                        break;
-                    }
-                }
-                if count > 100 { break; }
-                count += 1;
-            };
-            tmp_idx
+            Some(variants[rand::thread_rng().gen_range(0, variants.len())])
+        }
+    }
+
+    /// Returns the cells in which the opponent knows for sure there is no ship:
+    /// misses, as well as sunk ships and the area near them.
+    pub fn get_known_water(&self) -> Vec<usize> {
+        let mut water: Vec<usize> = self.misses_shots.clone();
+        for ship in self.ships.iter().filter(|ship| !ship.check_is_alive()) {
+            water.extend(ship.get_area_ship());
+            water.extend(ship.get_area_near_ship(None));
+        }
+        water
+    }
+
+    /// Returns for each cell the probability that it contains a deck,
+    /// calculated only from what the opponent knows about this fleet.
+    pub fn get_probability_map(&self) -> Vec<f64> {
+        let water: Vec<usize> = self.get_known_water();
+        let hits: Vec<usize> = self.ships.iter()
+            .filter(|ship| ship.check_is_wounded())
+            .flat_map(|ship| ship.get_fire_cell_idxes().clone())
+            .collect();
+        let remaining_ships: Vec<ShapeKind> = self.ships.iter()
+            .filter(|ship| ship.check_is_alive())
+            .map(|ship| ship.get_kind())
+            .collect();
+        calculate_probability_map(&FieldKnowledge {
+            width_field: self.width_field,
+            height_field: self.height_field,
+            water: &water,
+            hits: &hits,
+            remaining_ships: &remaining_ships,
+        })
+    }
+
+    /// Own shot in your fleet, the random choice among equal cells is made by the given generator.
+    /// Returns the index of the cell and the probability that it contains a deck,
+    /// or None if there is no cell left that may contain a deck.
+    pub fn generate_new_auto_shot_with_rng<R: Rng>(&self, rng: &mut R) -> Option<(usize, f64)> {
+        // Shoot at the cell with the highest probability of a deck,
+        // a random one of them if there are several.
+        let probability_map: Vec<f64> = self.get_probability_map();
+        let max_probability: f64 = probability_map.iter().cloned().fold(0.0, f64::max);
+        if max_probability > 0.0 {
+            let variant_shot: Vec<usize> = (0..probability_map.len())
+                .filter(|&idx| !self.is_cell_shot(idx))
+                .filter(|&idx| probability_map[idx] >= max_probability - f64::EPSILON)
+                .collect();
+            if !variant_shot.is_empty() {
+                return Some((variant_shot[rng.gen_range(0, variant_shot.len())], max_probability));
+            }
+        }
+        self.get_random_unknown_cell(rng).map(|idx_cell| (idx_cell, 0.0))
+    }
+
+    /// Own shot in your fleet made with the skill of the given level.
+    /// Returns the index of the cell and the probability that it contains a deck,
+    /// or None if there is no cell left that may contain a deck.
+    pub fn generate_new_auto_shot_for_level<R: Rng>(&self, ai_level: AiLevel, rng: &mut R) -> Option<(usize, f64)> {
+        let is_hunting: bool = match ai_level {
+            AiLevel::Easy => { true }
+            AiLevel::Normal => { !self.ships.iter().any(|ship| ship.check_is_wounded()) }
+            AiLevel::Hard => { false }
         };
-        result_val
-    }
-
-    /// Returns the vec of indices around the cell at the given index.
-    fn calculate_near_area(&self, idx: usize) -> Vec<usize> {
-        let mut variant_shot: Vec<usize> = Vec::new();
-        let x = idx % 10;
-        let y = idx / 10;
-        let x_end = if x + 1 <= 9 { x + 1 } else { x };
-        let x_start = if x == 0 { 0 } else { x - 1 };
-        let y_end = if y + 1 <= 9 { y + 1 } else { y };
-        let y_start = if y == 0 { 0 } else { y - 1 };
-        for x in x_start..=x_end {
-            for y in y_start..=y_end {
-                let tmp_idx = y * 10 + x;
-                if tmp_idx == idx { continue; };
-                // Check if the generated index is missing from the miss list
-                if self.misses_shots.iter().find(|&&idx| idx == (tmp_idx)).is_none() {
-                    if self.fire_shots.iter().find(|&&idx| idx == (tmp_idx)).is_none() {
-                        variant_shot.push(tmp_idx);
-                    }
+        if !is_hunting {
+            return self.generate_new_auto_shot_with_rng(rng);
+        }
+        let idx_cell: usize = self.get_random_unknown_cell(rng)?;
+        Some((idx_cell, self.get_probability_map().get(idx_cell).cloned().unwrap_or(0.0)))
+    }
+
+    /// Returns a random cell that may contain a deck,
+    /// or None if every cell has been shot or is known to be water.
+    fn get_random_unknown_cell<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        // Cells that have never been shot are preferred to cells with stale misses.
+        let water: Vec<usize> = self.get_known_water();
+        let unknown_cells: Vec<usize> = (0..self.width_field * self.height_field)
+            .filter(|&idx| !self.is_cell_shot(idx) && !water.contains(&idx))
+            .collect();
+        let never_shot_cells: Vec<usize> = unknown_cells.iter()
+            .filter(|idx| !self.stale_misses_shots.contains(idx))
+            .cloned()
+            .collect();
+        if !never_shot_cells.is_empty() {
+            Some(never_shot_cells[rng.gen_range(0, never_shot_cells.len())])
+        } else if !unknown_cells.is_empty() {
+            Some(unknown_cells[rng.gen_range(0, unknown_cells.len())])
+        } else {
+            None
+        }
+    }
+}
+
+/// Counts the shots the computer needs to sink the fleet with the given layout seed,
+/// a few shots at a time so that the page stays responsive.
+/// The result is the same for the same seed, so it can serve as a par for the player.
+pub struct ParCalculation {
+    target: Player,
+    rng: StdRng,
+    num_shots: usize,
+    // is_finished: "True" if the fleet is sunk or there is no cell left to shoot at.
+    is_finished: bool,
+}
+
+impl ParCalculation {
+    pub fn create(width_field: usize, height_field: usize, fleet: &[ShapeKind], seed: u64) -> Self {
+        Self {
+            target: Player::create("Par", false, width_field, height_field, fleet, Some(seed)),
+            rng: StdRng::seed_from_u64(seed),
+            num_shots: 0,
+            is_finished: false,
+        }
+    }
+
+    /// Makes at most the given number of shots.
+    /// Returns the par when the calculation is finished.
+    pub fn advance(&mut self, max_shots: usize) -> Option<usize> {
+        let size_field: usize = self.target.width_field * self.target.height_field;
+        for _ in 0..max_shots {
+            if self.target.get_num_living_ships() == 0 || self.num_shots >= size_field {
+                self.is_finished = true;
+                break;
+            }
+            match self.target.generate_new_auto_shot_with_rng(&mut self.rng) {
+                None => {
+                    self.is_finished = true;
+                    break;
+                }
+                Some((idx_cell, _)) => {
+                    self.target.process_a_shot(idx_cell);
+                    self.num_shots += 1;
                 }
             }
         }
-        variant_shot
+        if self.target.get_num_living_ships() == 0 {
+            self.is_finished = true;
+        }
+        if self.is_finished { Some(self.num_shots) } else { None }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a player on a 6x6 field with a 2-decker and a 1-decker.
+    fn create_player() -> Player {
+        Player::create("Player", false, 6, 6, &[ShapeKind::Straight(2), ShapeKind::Straight(1)], Some(3))
+    }
+
+    /// Sinks the 1-decker and misses next to it and far from it.
+    /// Returns the cells of the two misses.
+    fn sink_small_ship_and_miss(player: &mut Player) -> (usize, usize) {
+        let small_ship: Vec<usize> = player.ships[1].get_area_ship();
+        let area_near_small_ship: Vec<usize> = player.ships[1].get_area_near_ship(None);
+        let area_near_large_ship: Vec<usize> = player.ships[0].get_area_near_ship(None);
+        player.process_a_shot(small_ship[0]);
+        assert!(!player.ships[1].check_is_alive());
+        let idx_near: usize = area_near_small_ship[0];
+        let idx_far: usize = (0..36)
+            .find(|&idx| player.get_ship_idx_by_cell(idx).is_none()
+                && !area_near_small_ship.contains(&idx)
+                && !area_near_large_ship.contains(&idx))
+            .unwrap();
+        player.process_a_shot(idx_near);
+        player.process_a_shot(idx_far);
+        (idx_near, idx_far)
+    }
+
+    #[test]
+    fn keeps_misses_next_to_sunk_ships_known() {
+        let mut player = create_player();
+        let (idx_near, idx_far) = sink_small_ship_and_miss(&mut player);
+        assert_eq!(player.invalidate_misses(), vec![idx_far]);
+        assert!(player.is_cell_shot(idx_near));
+        assert!(!player.is_cell_shot(idx_far));
+        assert!(player.get_known_water().contains(&idx_near));
+        assert!(!player.get_known_water().contains(&idx_far));
+        // Nothing more becomes stale on the next maneuver.
+        assert!(player.invalidate_misses().is_empty());
+    }
+
+    #[test]
+    fn makes_misses_stale_after_maneuver() {
+        let mut player = create_player();
+        let (_, idx_far) = sink_small_ship_and_miss(&mut player);
+        let (idx_ship, maneuver) = player.generate_new_auto_maneuver().unwrap();
+        assert!(player.maneuver_ship(idx_ship, maneuver).is_some());
+        assert_eq!(player.invalidate_misses(), vec![idx_far]);
+        assert_eq!(player.stale_misses_shots, vec![idx_far]);
+        // The stale cell can be shot again, then its result is known anew.
+        player.process_a_shot(idx_far);
+        assert!(player.is_cell_shot(idx_far));
+        assert!(player.stale_misses_shots.is_empty());
+    }
+
+    #[test]
+    fn prefers_cells_never_shot_to_stale_misses() {
+        let mut player = create_player();
+        let (_, idx_far) = sink_small_ship_and_miss(&mut player);
+        player.invalidate_misses();
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..50 {
+            assert_ne!(player.get_random_unknown_cell(&mut rng), Some(idx_far));
+        }
+    }
+}
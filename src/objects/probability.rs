@@ -0,0 +1,77 @@
+use crate::objects::shape::{ShapeKind, get_area_near};
+
+// HIT_WEIGHT: How many times a placement covering a known hit is more likely
+// than a placement covering only unknown cells.
+const HIT_WEIGHT: f64 = 100.0;
+
+/// What the shooting player knows about the opponent's field.
+pub struct FieldKnowledge<'a> {
+    pub width_field: usize,
+    pub height_field: usize,
+    // water: Cells in which there is certainly no ship (misses, sunk ships and the area near them).
+    pub water: &'a [usize],
+    // hits: Hit decks of ships that have not been sunk yet.
+    pub hits: &'a [usize],
+    // remaining_ships: Kinds of ships that have not been sunk yet.
+    pub remaining_ships: &'a [ShapeKind],
+}
+
+/// Returns for each cell of the field the probability that it contains a deck of a ship.
+/// Known cells (water and hits) have zero probability.
+///
+/// For each remaining ship, all its placements consistent with the knowledge are enumerated,
+/// placements covering hits are considered much more likely.
+/// The probabilities of the individual ships are then combined as independent events.
+pub fn calculate_probability_map(knowledge: &FieldKnowledge) -> Vec<f64> {
+    let num_cells = knowledge.width_field * knowledge.height_field;
+    let mut is_water: Vec<bool> = vec![false; num_cells];
+    let mut is_hit: Vec<bool> = vec![false; num_cells];
+    for &idx in knowledge.water.iter().filter(|&&idx| idx < num_cells) {
+        is_water[idx] = true;
+    }
+    for &idx in knowledge.hits.iter().filter(|&&idx| idx < num_cells) {
+        is_hit[idx] = true;
+    }
+    let mut probability_no_deck: Vec<f64> = vec![1.0; num_cells];
+    for kind in knowledge.remaining_ships.iter() {
+        let mut weights: Vec<f64> = vec![0.0; num_cells];
+        let mut total_weight: f64 = 0.0;
+        for shape in kind.get_shape().get_orientations() {
+            for idx_origin in 0..num_cells {
+                let area = match shape.get_area(idx_origin, knowledge.width_field, knowledge.height_field) {
+                    None => { continue; }
+                    Some(area) => { area }
+                };
+                if area.iter().any(|&idx| is_water[idx]) {
+                    continue;
+                }
+                // A hit next to the ship would mean that another ship touches it.
+                let area_near = get_area_near(&area, knowledge.width_field, knowledge.height_field);
+                if area_near.iter().any(|&idx| is_hit[idx]) {
+                    continue;
+                }
+                let num_covered_hits = area.iter().filter(|&&idx| is_hit[idx]).count();
+                let weight = HIT_WEIGHT.powi(num_covered_hits as i32);
+                total_weight += weight;
+                for &idx in area.iter() {
+                    weights[idx] += weight;
+                }
+            }
+        }
+        if total_weight > 0.0 {
+            for (idx, weight) in weights.iter().enumerate() {
+                probability_no_deck[idx] *= 1.0 - weight / total_weight;
+            }
+        }
+    }
+    probability_no_deck.iter()
+        .enumerate()
+        .map(|(idx, p)| {
+            if is_hit[idx] || is_water[idx] {
+                0.0
+            } else {
+                1.0 - p
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use crate::objects::player::AiLevel;
+use crate::objects::rating::INITIAL_RATING;
+use crate::storage;
+use crate::i18n::{tr, trf};
+
+// KEY_PROFILES: The key of the profiles in the local storage.
+const KEY_PROFILES: &str = "sea_battle.profiles";
+// DEFAULT_PROFILE_NAME: The profile of the first human player if no profiles were created.
+pub const DEFAULT_PROFILE_NAME: &str = "Player";
+
+/// The result of a finished game for one human player.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    // date: The date of the game in the form "YYYY-MM-DD".
+    pub date: String,
+    // opponent: The level of the strongest computer opponent, or None if only humans were opponents.
+    pub opponent: Option<AiLevel>,
+    // rules: The description of the field, fleet and rules made by "settings::get_rules_id".
+    pub rules: String,
+    pub is_win: bool,
+    pub shots: usize,
+    pub hits: usize,
+    pub seconds: u64,
+}
+
+/// A named local profile with the history of its games.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub games: Vec<GameRecord>,
+    // rating: The Elo rating updated after every rated game.
+    #[serde(default = "get_initial_rating")]
+    pub rating: f64,
+}
+
+fn get_initial_rating() -> f64 {
+    INITIAL_RATING
+}
+
+/// The summary of a set of games.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Summary {
+    pub games: usize,
+    pub wins: usize,
+    pub shots: usize,
+    pub hits: usize,
+    // best_time: The fastest win in seconds.
+    pub best_time: Option<u64>,
+}
+
+impl Summary {
+    /// Sums up the given games.
+    pub fn from_games<'a>(games: impl Iterator<Item=&'a GameRecord>) -> Self {
+        let mut summary = Summary::default();
+        for game in games {
+            summary.games += 1;
+            summary.shots += game.shots;
+            summary.hits += game.hits;
+            if game.is_win {
+                summary.wins += 1;
+                summary.best_time = Some(summary.best_time.map_or(game.seconds, |best| best.min(game.seconds)));
+            }
+        }
+        summary
+    }
+
+    pub fn get_losses(&self) -> usize {
+        self.games - self.wins
+    }
+
+    /// Returns the share of hits among the shots from 0 to 1.
+    pub fn get_accuracy(&self) -> f64 {
+        if self.shots == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / self.shots as f64
+    }
+}
+
+impl Profile {
+    pub fn create(name: &str) -> Self {
+        Self { name: name.to_string(), games: Vec::new(), rating: INITIAL_RATING }
+    }
+
+    /// Returns the summary of all games of the profile.
+    pub fn get_summary(&self) -> Summary {
+        Summary::from_games(self.games.iter())
+    }
+
+    /// Returns the summaries of the games grouped by the opponent and the rules.
+    pub fn get_summaries_by_opponent_and_rules(&self) -> Vec<(Option<AiLevel>, String, Summary)> {
+        let mut groups: Vec<(Option<AiLevel>, String)> = Vec::new();
+        for game in self.games.iter() {
+            let group = (game.opponent, game.rules.clone());
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+        groups.into_iter()
+            .map(|(opponent, rules)| {
+                let summary = Summary::from_games(self.games.iter()
+                    .filter(|game| game.opponent == opponent && game.rules == rules));
+                (opponent, rules, summary)
+            })
+            .collect()
+    }
+}
+
+/// Returns the name of the opponent of the game to display to the player.
+pub fn get_opponent_name(opponent: Option<AiLevel>) -> String {
+    match opponent {
+        None => { tr("stats.humans") }
+        Some(ai_level) => { trf("stats.computer", &[ai_level.get_name().as_str()]) }
+    }
+}
+
+/// Restores the profiles from the local storage.
+pub fn load_profiles() -> Vec<Profile> {
+    storage::load(KEY_PROFILES)
+}
+
+/// Saves the profiles in the local storage.
+pub fn save_profiles(profiles: &[Profile]) {
+    storage::save(KEY_PROFILES, &profiles)
+}
+
+/// Adds the game to the history of the profile with the name, creating the profile if necessary.
+pub fn record_game(name: &str, record: GameRecord) {
+    let mut profiles: Vec<Profile> = load_profiles();
+    match profiles.iter_mut().find(|profile| profile.name == name) {
+        Some(profile) => { profile.games.push(record); }
+        None => {
+            let mut profile = Profile::create(name);
+            profile.games.push(record);
+            profiles.push(profile);
+        }
+    }
+    save_profiles(&profiles);
+}
+
+/// Returns the profiles as JSON text.
+pub fn export_profiles(profiles: &[Profile]) -> String {
+    storage::to_json(&profiles).unwrap_or_default()
+}
+
+/// Adds the profiles from JSON text, profiles with the same names are replaced.
+/// Returns the number of imported profiles, or None if the text cannot be read.
+pub fn import_profiles(text: &str) -> Option<usize> {
+    let imported: Vec<Profile> = storage::from_json(text)?;
+    let mut profiles: Vec<Profile> = load_profiles();
+    profiles.retain(|profile| !imported.iter().any(|other| other.name == profile.name));
+    let num_imported: usize = imported.len();
+    profiles.extend(imported);
+    save_profiles(&profiles);
+    Some(num_imported)
+}
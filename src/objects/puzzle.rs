@@ -0,0 +1,576 @@
+use rand::Rng;
+use crate::objects::ship::get_default_fleet;
+use crate::objects::shape::{ShapeKind, get_area_near};
+use crate::i18n::tr;
+
+// MAX_GENERATION_ATTEMPTS: How many puzzles are generated to find one of the requested difficulty.
+const MAX_GENERATION_ATTEMPTS: usize = 20;
+
+/// How hard it is to solve the puzzle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Difficulty {
+    // Easy: Solved by counting the decks in rows and columns.
+    Easy,
+    // Medium: Also requires checking where the remaining ships can fit.
+    Medium,
+    // Hard: Requires trying out the options.
+    Hard,
+}
+
+impl Difficulty {
+    pub fn get_name(&self) -> String {
+        match self {
+            Difficulty::Easy => tr("puzzle.easy"),
+            Difficulty::Medium => tr("puzzle.medium"),
+            Difficulty::Hard => tr("puzzle.hard"),
+        }
+    }
+}
+
+/// The mark of the player on a cell of the puzzle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mark {
+    Unknown,
+    Water,
+    Ship,
+}
+
+/// Why the hint is true.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HintReason {
+    // Mistake: The mark of the player on the cell is wrong.
+    Mistake,
+    // LineFull: All decks of the row or column are already found.
+    LineFull,
+    // LineNeedsAll: The row or column needs all of its unknown cells.
+    LineNeedsAll,
+    // NearShip: The cell touches a deck diagonally, ships do not touch.
+    NearShip,
+    // NoShipFits: None of the remaining ships can be placed on the cell.
+    NoShipFits,
+    // Solution: The cell is simply taken from the solution.
+    Solution,
+}
+
+/// The cell that the player can mark next and the mark.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hint {
+    pub idx_cell: usize,
+    pub mark: Mark,
+    pub reason: HintReason,
+}
+
+/// Battleship solitaire: a hidden fleet is found from the numbers of decks
+/// in each row and column and a few revealed cells.
+pub struct Puzzle {
+    width_field: usize,
+    height_field: usize,
+    fleet: Vec<ShapeKind>,
+    // solution: For each cell, "True" if it contains a deck.
+    solution: Vec<bool>,
+    row_counts: Vec<usize>,
+    column_counts: Vec<usize>,
+    // revealed: Cells shown to the player from the start.
+    revealed: Vec<usize>,
+    difficulty: Difficulty,
+}
+
+impl Puzzle {
+    /// Generates a puzzle with exactly one solution,
+    /// trying to match the requested difficulty.
+    pub fn generate(width_field: usize, height_field: usize, fleet: &[ShapeKind], difficulty: Difficulty) -> Self {
+        let mut best_puzzle: Option<Puzzle> = None;
+        for _ in 0..MAX_GENERATION_ATTEMPTS {
+            let mut puzzle = Puzzle::generate_unique(width_field, height_field, fleet);
+            match difficulty {
+                Difficulty::Easy => { puzzle.reveal_until_deducible(false); }
+                Difficulty::Medium => { puzzle.reveal_until_deducible(true); }
+                Difficulty::Hard => {}
+            }
+            if puzzle.difficulty == difficulty {
+                return puzzle;
+            }
+            // Otherwise we keep the puzzle closest to the requested difficulty.
+            let distance = |p: &Puzzle| (p.difficulty as i32 - difficulty as i32).abs();
+            if best_puzzle.as_ref().map_or(true, |best| distance(&puzzle) < distance(best)) {
+                best_puzzle = Some(puzzle);
+            }
+        }
+        best_puzzle.unwrap_or_else(|| Puzzle::generate_unique(width_field, height_field, fleet))
+    }
+
+    /// Generates a random fleet and reveals cells until the solution becomes unique.
+    fn generate_unique(width_field: usize, height_field: usize, fleet: &[ShapeKind]) -> Self {
+        let mut rng = rand::thread_rng();
+        let ships = get_default_fleet(width_field, height_field, fleet, None);
+        let mut solution: Vec<bool> = vec![false; width_field * height_field];
+        for ship in ships.iter() {
+            for idx in ship.get_area_ship() {
+                solution[idx] = true;
+            }
+        }
+        let mut puzzle = Puzzle {
+            width_field,
+            height_field,
+            fleet: ships.iter().map(|ship| ship.get_kind()).collect(),
+            row_counts: (0..height_field)
+                .map(|y| (0..width_field).filter(|&x| solution[y * width_field + x]).count())
+                .collect(),
+            column_counts: (0..width_field)
+                .map(|x| (0..height_field).filter(|&y| solution[y * width_field + x]).count())
+                .collect(),
+            solution,
+            revealed: Vec::new(),
+            difficulty: Difficulty::Hard,
+        };
+        loop {
+            let solutions = puzzle.find_solutions(&puzzle.get_initial_marks(), 2);
+            if solutions.len() < 2 {
+                break;
+            }
+            // Reveal one of the cells in which the two solutions differ.
+            let different_cells: Vec<usize> = (0..puzzle.solution.len())
+                .filter(|&idx| solutions[0][idx] != solutions[1][idx])
+                .collect();
+            puzzle.revealed.push(different_cells[rng.gen_range(0, different_cells.len())]);
+        }
+        puzzle.difficulty = puzzle.grade();
+        puzzle
+    }
+
+    /// Reveals cells until the puzzle is solved by deduction alone,
+    /// with or without the rule of the fitting of the remaining ships.
+    fn reveal_until_deducible(&mut self, is_fitting_used: bool) {
+        let mut rng = rand::thread_rng();
+        loop {
+            let marks = self.deduce(&self.get_initial_marks(), is_fitting_used).0;
+            let unknown_cells: Vec<usize> = (0..marks.len())
+                .filter(|&idx| marks[idx] == Mark::Unknown)
+                .collect();
+            if unknown_cells.is_empty() {
+                break;
+            }
+            self.revealed.push(unknown_cells[rng.gen_range(0, unknown_cells.len())]);
+        }
+        self.difficulty = self.grade();
+    }
+
+    pub fn get_width_field(&self) -> usize { self.width_field }
+
+    pub fn get_height_field(&self) -> usize { self.height_field }
+
+    pub fn get_fleet(&self) -> &Vec<ShapeKind> { self.fleet.as_ref() }
+
+    pub fn get_row_counts(&self) -> &Vec<usize> { self.row_counts.as_ref() }
+
+    pub fn get_column_counts(&self) -> &Vec<usize> { self.column_counts.as_ref() }
+
+    pub fn get_difficulty(&self) -> Difficulty { self.difficulty }
+
+    pub fn is_revealed(&self, idx_cell: usize) -> bool {
+        self.revealed.contains(&idx_cell)
+    }
+
+    /// Returns the correct mark of the cell.
+    pub fn get_solution_mark(&self, idx_cell: usize) -> Mark {
+        if self.solution[idx_cell] { Mark::Ship } else { Mark::Water }
+    }
+
+    /// Returns the marks with only the revealed cells known.
+    pub fn get_initial_marks(&self) -> Vec<Mark> {
+        (0..self.solution.len())
+            .map(|idx| if self.is_revealed(idx) { self.get_solution_mark(idx) } else { Mark::Unknown })
+            .collect()
+    }
+
+    /// Returns the indexes of the cells marked incorrectly.
+    pub fn get_mistakes(&self, marks: &[Mark]) -> Vec<usize> {
+        (0..self.solution.len())
+            .filter(|&idx| marks[idx] != Mark::Unknown && marks[idx] != self.get_solution_mark(idx))
+            .collect()
+    }
+
+    /// Returns "True" if all cells are marked correctly.
+    pub fn is_solved(&self, marks: &[Mark]) -> bool {
+        (0..self.solution.len()).all(|idx| marks[idx] == self.get_solution_mark(idx))
+    }
+
+    /// Returns the next cell the player can mark, with the explanation.
+    /// Mistakes are pointed out first, then deductions from the marks, and the solution at last.
+    pub fn get_hint(&self, marks: &[Mark]) -> Option<Hint> {
+        if let Some(&idx_cell) = self.get_mistakes(marks).first() {
+            return Some(Hint { idx_cell, mark: self.get_solution_mark(idx_cell), reason: HintReason::Mistake });
+        }
+        if let Some(hint) = self.deduce(marks, true).1 {
+            return Some(hint);
+        }
+        (0..self.solution.len())
+            .find(|&idx| marks[idx] == Mark::Unknown)
+            .map(|idx_cell| Hint { idx_cell, mark: self.get_solution_mark(idx_cell), reason: HintReason::Solution })
+    }
+
+    /// Grades the puzzle by the rules needed to solve it from the revealed cells.
+    fn grade(&self) -> Difficulty {
+        let marks = self.get_initial_marks();
+        if !self.deduce(&marks, false).0.contains(&Mark::Unknown) {
+            Difficulty::Easy
+        } else if !self.deduce(&marks, true).0.contains(&Mark::Unknown) {
+            Difficulty::Medium
+        } else {
+            Difficulty::Hard
+        }
+    }
+
+    /// Applies the rules of deduction to the marks as long as something new is found.
+    /// If "is_fitting_used" is "True", then the rule of the fitting of the remaining ships is also used.
+    /// Returns the resulting marks and the first deduction as a hint.
+    fn deduce(&self, marks: &[Mark], is_fitting_used: bool) -> (Vec<Mark>, Option<Hint>) {
+        let mut marks: Vec<Mark> = marks.to_vec();
+        let mut first_hint: Option<Hint> = None;
+        loop {
+            let mut deductions = self.deduce_by_lines(&marks);
+            if deductions.is_empty() {
+                deductions = self.deduce_near_ships(&marks);
+            }
+            if deductions.is_empty() && is_fitting_used {
+                deductions = self.deduce_by_fitting(&marks);
+            }
+            if deductions.is_empty() {
+                return (marks, first_hint);
+            }
+            if first_hint.is_none() {
+                first_hint = deductions.first().cloned();
+            }
+            for hint in deductions {
+                marks[hint.idx_cell] = hint.mark;
+            }
+        }
+    }
+
+    /// Returns the cells of the line (row or column) with the given index.
+    fn get_line(&self, is_row: bool, idx_line: usize) -> Vec<usize> {
+        if is_row {
+            (0..self.width_field).map(|x| idx_line * self.width_field + x).collect()
+        } else {
+            (0..self.height_field).map(|y| y * self.width_field + idx_line).collect()
+        }
+    }
+
+    /// Rows and columns: if all decks are found, the rest is water;
+    /// if all unknown cells are needed, they are decks.
+    fn deduce_by_lines(&self, marks: &[Mark]) -> Vec<Hint> {
+        let mut deductions: Vec<Hint> = Vec::new();
+        let lines = (0..self.height_field).map(|y| (true, y, self.row_counts[y]))
+            .chain((0..self.width_field).map(|x| (false, x, self.column_counts[x])));
+        for (is_row, idx_line, count) in lines {
+            let cells = self.get_line(is_row, idx_line);
+            let num_ships = cells.iter().filter(|&&idx| marks[idx] == Mark::Ship).count();
+            let unknown: Vec<usize> = cells.iter().cloned().filter(|&idx| marks[idx] == Mark::Unknown).collect();
+            if unknown.is_empty() {
+                continue;
+            }
+            let (mark, reason) = if num_ships == count {
+                (Mark::Water, HintReason::LineFull)
+            } else if num_ships + unknown.len() == count {
+                (Mark::Ship, HintReason::LineNeedsAll)
+            } else {
+                continue;
+            };
+            for idx_cell in unknown {
+                if !deductions.iter().any(|hint| hint.idx_cell == idx_cell) {
+                    deductions.push(Hint { idx_cell, mark, reason });
+                }
+            }
+        }
+        deductions
+    }
+
+    /// Cells diagonal to a deck are water, if all ships are straight.
+    fn deduce_near_ships(&self, marks: &[Mark]) -> Vec<Hint> {
+        let mut deductions: Vec<Hint> = Vec::new();
+        if !self.fleet.iter().all(|kind| matches!(kind, ShapeKind::Straight(_))) {
+            return deductions;
+        }
+        for idx in (0..marks.len()).filter(|&idx| marks[idx] == Mark::Ship) {
+            let x = idx % self.width_field;
+            let y = idx / self.width_field;
+            for &(dx, dy) in [(-1i32, -1i32), (1, -1), (-1, 1), (1, 1)].iter() {
+                let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+                if nx < 0 || ny < 0 || nx >= self.width_field as i32 || ny >= self.height_field as i32 {
+                    continue;
+                }
+                let idx_cell = ny as usize * self.width_field + nx as usize;
+                if marks[idx_cell] == Mark::Unknown && !deductions.iter().any(|hint| hint.idx_cell == idx_cell) {
+                    deductions.push(Hint { idx_cell, mark: Mark::Water, reason: HintReason::NearShip });
+                }
+            }
+        }
+        deductions
+    }
+
+    /// Unknown cells that no ship of the fleet can cover are water.
+    fn deduce_by_fitting(&self, marks: &[Mark]) -> Vec<Hint> {
+        let mut is_coverable: Vec<bool> = vec![false; marks.len()];
+        let mut kinds: Vec<ShapeKind> = self.fleet.clone();
+        kinds.sort();
+        kinds.dedup();
+        for kind in kinds.iter() {
+            for area in self.get_placements(*kind, marks) {
+                // The ship must not touch decks outside of it.
+                let area_near = get_area_near(&area, self.width_field, self.height_field);
+                if area_near.iter().any(|&idx| marks[idx] == Mark::Ship) {
+                    continue;
+                }
+                for idx in area {
+                    is_coverable[idx] = true;
+                }
+            }
+        }
+        (0..marks.len())
+            .filter(|&idx| marks[idx] == Mark::Unknown && !is_coverable[idx])
+            .map(|idx_cell| Hint { idx_cell, mark: Mark::Water, reason: HintReason::NoShipFits })
+            .collect()
+    }
+
+    /// Returns all placements of the ship that do not cover water
+    /// and do not exceed the numbers of decks in rows and columns.
+    fn get_placements(&self, kind: ShapeKind, marks: &[Mark]) -> Vec<Vec<usize>> {
+        let mut placements: Vec<Vec<usize>> = Vec::new();
+        for shape in kind.get_shape().get_orientations() {
+            for idx_origin in 0..marks.len() {
+                let area = match shape.get_area(idx_origin, self.width_field, self.height_field) {
+                    None => { continue; }
+                    Some(area) => { area }
+                };
+                if area.iter().any(|&idx| marks[idx] == Mark::Water) {
+                    continue;
+                }
+                let fits_counts = (0..self.height_field).all(|y| {
+                    area.iter().filter(|&&idx| idx / self.width_field == y).count() <= self.row_counts[y]
+                }) && (0..self.width_field).all(|x| {
+                    area.iter().filter(|&&idx| idx % self.width_field == x).count() <= self.column_counts[x]
+                });
+                if fits_counts {
+                    placements.push(area);
+                }
+            }
+        }
+        placements
+    }
+
+    /// Finds up to "limit" different layouts of the fleet consistent with the marks.
+    fn find_solutions(&self, marks: &[Mark], limit: usize) -> Vec<Vec<bool>> {
+        // Large ships are placed first, identical ships go one after another and are placed
+        // in increasing order of their placements, so that the same layout is not found twice.
+        let mut fleet: Vec<ShapeKind> = self.fleet.clone();
+        fleet.sort_by_key(|&kind| (std::cmp::Reverse(kind.get_shape().get_size()), kind));
+        let placements: Vec<Vec<Vec<usize>>> = fleet.iter()
+            .map(|&kind| self.get_placements(kind, marks))
+            .collect();
+        let mut search = SolutionSearch {
+            puzzle: self,
+            marks,
+            fleet: &fleet,
+            placements: &placements,
+            is_deck: vec![false; marks.len()],
+            num_blocks: vec![0; marks.len()],
+            row_used: vec![0; self.height_field],
+            column_used: vec![0; self.width_field],
+            solutions: Vec::new(),
+            limit,
+        };
+        search.place(0, 0);
+        search.solutions
+    }
+}
+
+/// The state of the search of the layouts of the fleet by backtracking.
+struct SolutionSearch<'a> {
+    puzzle: &'a Puzzle,
+    marks: &'a [Mark],
+    fleet: &'a [ShapeKind],
+    placements: &'a [Vec<Vec<usize>>],
+    is_deck: Vec<bool>,
+    // num_blocks: For each cell, how many placed ships occupy it or touch it.
+    num_blocks: Vec<usize>,
+    row_used: Vec<usize>,
+    column_used: Vec<usize>,
+    solutions: Vec<Vec<bool>>,
+    limit: usize,
+}
+
+impl<'a> SolutionSearch<'a> {
+    /// Places the ship with the index "idx_ship" starting from the placement "idx_first_placement".
+    fn place(&mut self, idx_ship: usize, idx_first_placement: usize) {
+        if self.solutions.len() >= self.limit {
+            return;
+        }
+        if idx_ship == self.fleet.len() {
+            self.check_solution();
+            return;
+        }
+        let width_field = self.puzzle.width_field;
+        let height_field = self.puzzle.height_field;
+        for idx_placement in idx_first_placement..self.placements[idx_ship].len() {
+            let area: &Vec<usize> = &self.placements[idx_ship][idx_placement];
+            if area.iter().any(|&idx| self.num_blocks[idx] > 0) {
+                continue;
+            }
+            let fits_counts = area.iter().all(|&idx| {
+                let (x, y) = (idx % width_field, idx / width_field);
+                self.row_used[y] + area.iter().filter(|&&i| i / width_field == y).count() <= self.puzzle.row_counts[y]
+                    && self.column_used[x] + area.iter().filter(|&&i| i % width_field == x).count()
+                    <= self.puzzle.column_counts[x]
+            });
+            if !fits_counts {
+                continue;
+            }
+            let area: Vec<usize> = area.clone();
+            let area_near = get_area_near(&area, width_field, height_field);
+            // A revealed deck next to the ship would mean that ships touch.
+            if area_near.iter().any(|&idx| self.marks[idx] == Mark::Ship) {
+                continue;
+            }
+            self.set_ship(&area, &area_near, true);
+            let is_same_kind = self.fleet.get(idx_ship + 1) == Some(&self.fleet[idx_ship]);
+            self.place(idx_ship + 1, if is_same_kind { idx_placement + 1 } else { 0 });
+            self.set_ship(&area, &area_near, false);
+            if self.solutions.len() >= self.limit {
+                return;
+            }
+        }
+    }
+
+    /// Places or removes the ship.
+    fn set_ship(&mut self, area: &[usize], area_near: &[usize], is_placed: bool) {
+        let width_field = self.puzzle.width_field;
+        for &idx in area.iter().chain(area_near.iter()) {
+            if is_placed { self.num_blocks[idx] += 1; } else { self.num_blocks[idx] -= 1; }
+        }
+        for &idx in area {
+            self.is_deck[idx] = is_placed;
+            if is_placed {
+                self.row_used[idx / width_field] += 1;
+                self.column_used[idx % width_field] += 1;
+            } else {
+                self.row_used[idx / width_field] -= 1;
+                self.column_used[idx % width_field] -= 1;
+            }
+        }
+    }
+
+    /// Saves the layout if all numbers and revealed decks match.
+    fn check_solution(&mut self) {
+        let is_valid = self.row_used == self.puzzle.row_counts
+            && self.column_used == self.puzzle.column_counts
+            && (0..self.marks.len()).all(|idx| self.marks[idx] != Mark::Ship || self.is_deck[idx]);
+        if is_valid {
+            self.solutions.push(self.is_deck.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Makes a puzzle from the rows of its solution, "#" is a deck.
+    fn create_puzzle(rows: &[&str], fleet: &[ShapeKind], revealed: &[usize]) -> Puzzle {
+        let width_field: usize = rows[0].len();
+        let height_field: usize = rows.len();
+        let solution: Vec<bool> = rows.iter().flat_map(|row| row.chars().map(|c| c == '#')).collect();
+        Puzzle {
+            width_field,
+            height_field,
+            fleet: fleet.to_vec(),
+            row_counts: (0..height_field)
+                .map(|y| (0..width_field).filter(|&x| solution[y * width_field + x]).count())
+                .collect(),
+            column_counts: (0..width_field)
+                .map(|x| (0..height_field).filter(|&y| solution[y * width_field + x]).count())
+                .collect(),
+            solution,
+            revealed: revealed.to_vec(),
+            difficulty: Difficulty::Hard,
+        }
+    }
+
+    #[test]
+    fn finds_unique_solution() {
+        let puzzle = create_puzzle(&[
+            "##..",
+            "....",
+            "....",
+            "....",
+        ], &[ShapeKind::Straight(2)], &[]);
+        let solutions = puzzle.find_solutions(&puzzle.get_initial_marks(), 2);
+        assert_eq!(solutions, vec![puzzle.solution.clone()]);
+    }
+
+    #[test]
+    fn finds_several_solutions() {
+        let puzzle = create_puzzle(&[
+            "#...",
+            "....",
+            "..#.",
+            "....",
+        ], &[ShapeKind::Straight(1), ShapeKind::Straight(1)], &[]);
+        assert_eq!(puzzle.find_solutions(&puzzle.get_initial_marks(), 10).len(), 2);
+        // Revealing one of the decks leaves only one layout.
+        let puzzle = create_puzzle(&[
+            "#...",
+            "....",
+            "..#.",
+            "....",
+        ], &[ShapeKind::Straight(1), ShapeKind::Straight(1)], &[0]);
+        assert_eq!(puzzle.find_solutions(&puzzle.get_initial_marks(), 10).len(), 1);
+    }
+
+    #[test]
+    fn counts_layout_of_identical_ships_once_in_unsorted_fleet() {
+        let puzzle = create_puzzle(&[
+            "##.##.####",
+            "##.##.....",
+        ], &[ShapeKind::Square, ShapeKind::Straight(4), ShapeKind::Square], &[]);
+        assert_eq!(puzzle.find_solutions(&puzzle.get_initial_marks(), 2).len(), 1);
+    }
+
+    #[test]
+    fn gives_hints_by_deduction() {
+        let puzzle = create_puzzle(&[
+            "##..",
+            "....",
+            "....",
+            "....",
+        ], &[ShapeKind::Straight(2)], &[]);
+        let marks = puzzle.get_initial_marks();
+        let hint = puzzle.get_hint(&marks).unwrap();
+        assert_eq!(hint.reason, HintReason::LineFull);
+        assert_eq!(hint.mark, Mark::Water);
+        assert_eq!(puzzle.grade(), Difficulty::Easy);
+        // A wrong mark is pointed out first.
+        let mut marks = marks;
+        marks[0] = Mark::Water;
+        assert_eq!(puzzle.get_hint(&marks).map(|hint| hint.reason), Some(HintReason::Mistake));
+    }
+
+    #[test]
+    fn deduces_water_where_no_ship_fits() {
+        let puzzle = create_puzzle(&[
+            "##.##.####",
+            "##.##.....",
+        ], &[ShapeKind::Square, ShapeKind::Straight(4), ShapeKind::Square], &[]);
+        let marks: Vec<Mark> = puzzle.deduce(&puzzle.get_initial_marks(), true).0;
+        assert!(puzzle.get_mistakes(&marks).is_empty());
+        assert_eq!(marks[15], Mark::Water);
+    }
+
+    #[test]
+    fn generates_puzzles_with_one_solution() {
+        for fleet in [crate::objects::shape::get_classic_fleet(), crate::objects::shape::get_shapes_fleet()].iter() {
+            let puzzle = Puzzle::generate(10, 10, fleet, Difficulty::Medium);
+            let solutions = puzzle.find_solutions(&puzzle.get_initial_marks(), 2);
+            assert_eq!(solutions, vec![puzzle.solution.clone()]);
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use serde::{Deserialize, Serialize};
+use crate::objects::player::{Player, AiLevel};
+use crate::objects::game::{Game, AutoMove};
+use crate::objects::profile::{self, Profile};
+use crate::objects::shape::get_classic_fleet;
+use crate::storage;
+
+// KEY_AI_RATINGS: The key of the ratings of the computer levels in the local storage.
+const KEY_AI_RATINGS: &str = "sea_battle.ai_ratings";
+// INITIAL_RATING: The rating of a new profile and the average rating of the computer levels.
+pub const INITIAL_RATING: f64 = 1200.0;
+// K_FACTOR: How much one game changes the rating.
+const K_FACTOR: f64 = 32.0;
+// NUM_CALIBRATION_ROUNDS: How many times each pair of computer levels plays during the calibration.
+const NUM_CALIBRATION_ROUNDS: usize = 10;
+// STARTING_AI_RATINGS: The ratings of the computer levels found by self-play on the classic field,
+//                      used until they are calibrated on the device.
+const STARTING_AI_RATINGS: [(AiLevel, f64); 3] = [
+    (AiLevel::Easy, 840.0),
+    (AiLevel::Normal, 1320.0),
+    (AiLevel::Hard, 1440.0),
+];
+
+/// The rating of a computer level.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AiRating {
+    pub ai_level: AiLevel,
+    pub rating: f64,
+}
+
+/// A side of a rated game.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Participant {
+    // Profile: The name of the profile of a human player.
+    Profile(String),
+    Computer(AiLevel),
+}
+
+/// Returns the expected score of the player against the opponent, from 0 to 1.
+pub fn get_expected_score(rating: f64, rating_opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((rating_opponent - rating) / 400.0))
+}
+
+/// Returns the new ratings of the two players after a game,
+/// "score" is 1 if the first player won and 0 if he lost.
+pub fn update_ratings(rating: f64, rating_opponent: f64, score: f64) -> (f64, f64) {
+    let change: f64 = K_FACTOR * (score - get_expected_score(rating, rating_opponent));
+    (rating + change, rating_opponent - change)
+}
+
+/// Restores the ratings of the computer levels,
+/// the starting ratings are saved if they were never saved.
+pub fn load_ai_ratings() -> Vec<AiRating> {
+    let ai_ratings: Vec<AiRating> = storage::load(KEY_AI_RATINGS);
+    if AiLevel::all().iter().all(|ai_level| ai_ratings.iter().any(|ai_rating| ai_rating.ai_level == *ai_level)) {
+        return ai_ratings;
+    }
+    let ai_ratings: Vec<AiRating> = STARTING_AI_RATINGS.iter()
+        .map(|&(ai_level, rating)| AiRating { ai_level, rating })
+        .collect();
+    storage::save(KEY_AI_RATINGS, &ai_ratings);
+    ai_ratings
+}
+
+/// Returns the rating of the participant.
+pub fn get_rating(participant: &Participant) -> f64 {
+    find_rating(&profile::load_profiles(), &load_ai_ratings(), participant)
+}
+
+/// Returns the rating of the participant among the profiles and the computer levels,
+/// the initial rating if there is no such participant.
+fn find_rating(profiles: &[Profile], ai_ratings: &[AiRating], participant: &Participant) -> f64 {
+    match participant {
+        Participant::Profile(name) => {
+            profiles.iter()
+                .find(|profile| profile.name == *name)
+                .map_or(INITIAL_RATING, |profile| profile.rating)
+        }
+        Participant::Computer(ai_level) => {
+            ai_ratings.iter()
+                .find(|ai_rating| ai_rating.ai_level == *ai_level)
+                .map_or(INITIAL_RATING, |ai_rating| ai_rating.rating)
+        }
+    }
+}
+
+/// Updates and saves the ratings of both sides of a rated game.
+/// Returns an error if the rating of a side could not be saved, the other side is still updated.
+pub fn record_rated_game(winner: &Participant, loser: &Participant) -> Result<(), String> {
+    let mut profiles: Vec<Profile> = profile::load_profiles();
+    let mut ai_ratings: Vec<AiRating> = load_ai_ratings();
+    let result = apply_rated_game(&mut profiles, &mut ai_ratings, winner, loser);
+    profile::save_profiles(&profiles);
+    storage::save(KEY_AI_RATINGS, &ai_ratings);
+    result
+}
+
+/// Updates the ratings of both sides of a rated game among the profiles and the computer levels.
+/// Returns an error if a side is missing, the other side is still updated.
+fn apply_rated_game(
+    profiles: &mut [Profile],
+    ai_ratings: &mut [AiRating],
+    winner: &Participant,
+    loser: &Participant,
+) -> Result<(), String> {
+    let (rating_winner, rating_loser) = update_ratings(
+        find_rating(profiles, ai_ratings, winner),
+        find_rating(profiles, ai_ratings, loser),
+        1.0,
+    );
+    let result_winner = set_rating(profiles, ai_ratings, winner, rating_winner);
+    let result_loser = set_rating(profiles, ai_ratings, loser, rating_loser);
+    result_winner.and(result_loser)
+}
+
+/// Sets the new rating of the participant among the profiles and the computer levels.
+/// Returns an error if there is no such profile or computer level.
+fn set_rating(
+    profiles: &mut [Profile],
+    ai_ratings: &mut [AiRating],
+    participant: &Participant,
+    rating: f64,
+) -> Result<(), String> {
+    match participant {
+        Participant::Profile(name) => {
+            match profiles.iter_mut().find(|profile| profile.name == *name) {
+                None => { return Err(format!("The rating of the missing profile \"{}\" was not saved", name)); }
+                Some(profile) => { profile.rating = rating; }
+            }
+        }
+        Participant::Computer(ai_level) => {
+            match ai_ratings.iter_mut().find(|ai_rating| ai_rating.ai_level == *ai_level) {
+                None => { return Err(format!("The rating of the computer level {:?} was not saved", ai_level)); }
+                Some(ai_rating) => { ai_rating.rating = rating; }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the computer level whose rating is closest to the given one.
+pub fn get_closest_ai_level(rating: f64) -> AiLevel {
+    load_ai_ratings().iter()
+        .min_by(|a, b| (a.rating - rating).abs().partial_cmp(&(b.rating - rating).abs())
+            .unwrap_or(std::cmp::Ordering::Equal))
+        .map_or(AiLevel::Normal, |ai_rating| ai_rating.ai_level)
+}
+
+/// Rates the computer levels by games against each other on the classic field.
+/// The games are played one at a time, so that the page stays responsive between them.
+pub struct Calibration {
+    ai_levels: Vec<AiLevel>,
+    ratings: Vec<f64>,
+    // games: The indexes of the levels that play each game, the first one moves first.
+    games: Vec<(usize, usize)>,
+    num_played: usize,
+}
+
+impl Calibration {
+    pub fn create() -> Self {
+        let ai_levels: Vec<AiLevel> = AiLevel::all();
+        let mut games: Vec<(usize, usize)> = Vec::new();
+        for round in 0..NUM_CALIBRATION_ROUNDS {
+            for idx in 0..ai_levels.len() {
+                for idx_other in (idx + 1)..ai_levels.len() {
+                    // The levels take turns to move first.
+                    games.push(if round % 2 == 0 { (idx, idx_other) } else { (idx_other, idx) });
+                }
+            }
+        }
+        Self {
+            ratings: vec![INITIAL_RATING; ai_levels.len()],
+            ai_levels,
+            games,
+            num_played: 0,
+        }
+    }
+
+    /// Returns the number of games played and the number of all games.
+    pub fn get_progress(&self) -> (usize, usize) {
+        (self.num_played, self.games.len())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.num_played >= self.games.len()
+    }
+
+    /// Plays the next game and updates the ratings of its sides.
+    pub fn play_next_game(&mut self) {
+        let (first, second) = match self.games.get(self.num_played) {
+            None => { return; }
+            Some(&game) => { game }
+        };
+        let is_first_winner: bool = play_self_game(self.ai_levels[first], self.ai_levels[second]);
+        let (idx_winner, idx_loser) = if is_first_winner { (first, second) } else { (second, first) };
+        let (rating_winner, rating_loser) = update_ratings(self.ratings[idx_winner], self.ratings[idx_loser], 1.0);
+        self.ratings[idx_winner] = rating_winner;
+        self.ratings[idx_loser] = rating_loser;
+        self.num_played += 1;
+    }
+
+    /// Saves and returns the ratings shifted so that their average is the initial rating.
+    pub fn save(&self) -> Vec<AiRating> {
+        let shift: f64 = INITIAL_RATING - self.ratings.iter().sum::<f64>() / self.ratings.len() as f64;
+        let ai_ratings: Vec<AiRating> = self.ai_levels.iter().zip(self.ratings.iter())
+            .map(|(&ai_level, &rating)| AiRating { ai_level, rating: rating + shift })
+            .collect();
+        storage::save(KEY_AI_RATINGS, &ai_ratings);
+        ai_ratings
+    }
+}
+
+/// Plays a game between two computer levels on the classic field.
+/// Returns "True" if the first one won.
+fn play_self_game(ai_level_first: AiLevel, ai_level_second: AiLevel) -> bool {
+    let players: Vec<Player> = [ai_level_first, ai_level_second].iter()
+        .map(|&ai_level| {
+            let mut player = Player::create(ai_level.get_name().as_str(), false, 10, 10, &get_classic_fleet(), None);
+            player.set_ai_level(ai_level);
+            player
+        })
+        .collect();
+    let mut game = Game::create(players, &[None, None], false);
+    while let Some(auto_move) = game.generate_auto_move() {
+        if let AutoMove::Shot(idx_target, idx_cell) = auto_move {
+            game.shoot(idx_target, idx_cell);
+        }
+    }
+    game.get_winners().contains(&0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the ratings of all computer levels equal to the given one.
+    fn create_ai_ratings(rating: f64) -> Vec<AiRating> {
+        AiLevel::all().iter().map(|&ai_level| AiRating { ai_level, rating }).collect()
+    }
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!((value - expected).abs() < 1e-6, "{} is not {}", value, expected);
+    }
+
+    #[test]
+    fn expects_scores_by_rating_difference() {
+        assert_close(get_expected_score(1200.0, 1200.0), 0.5);
+        // A difference of 400 points makes the stronger player ten times more likely to win.
+        assert_close(get_expected_score(1600.0, 1200.0), 10.0 / 11.0);
+        assert_close(get_expected_score(1200.0, 1600.0), 1.0 / 11.0);
+        assert_close(get_expected_score(1400.0, 1300.0) + get_expected_score(1300.0, 1400.0), 1.0);
+    }
+
+    #[test]
+    fn updates_ratings_by_k_factor() {
+        // Equal players exchange half of the K-factor.
+        let (rating, rating_opponent) = update_ratings(1200.0, 1200.0, 1.0);
+        assert_close(rating, 1216.0);
+        assert_close(rating_opponent, 1184.0);
+        // The loss mirrors the win.
+        let (rating, rating_opponent) = update_ratings(1200.0, 1200.0, 0.0);
+        assert_close(rating, 1184.0);
+        assert_close(rating_opponent, 1216.0);
+        // A draw changes nothing between equal players and favours the weaker one otherwise.
+        let (rating, rating_opponent) = update_ratings(1200.0, 1200.0, 0.5);
+        assert_close(rating, 1200.0);
+        assert_close(rating_opponent, 1200.0);
+        let (rating, rating_opponent) = update_ratings(1000.0, 1400.0, 0.5);
+        assert_close(rating, 1000.0 + K_FACTOR * (0.5 - 1.0 / 11.0));
+        assert_close(rating_opponent, 1400.0 - K_FACTOR * (0.5 - 1.0 / 11.0));
+    }
+
+    #[test]
+    fn keeps_sum_of_ratings() {
+        let games: [(f64, f64, f64); 3] = [(900.0, 1500.0, 1.0), (1500.0, 900.0, 1.0), (1300.0, 1250.0, 0.0)];
+        for &(rating, rating_opponent, score) in games.iter() {
+            let (new_rating, new_rating_opponent) = update_ratings(rating, rating_opponent, score);
+            assert_close(new_rating + new_rating_opponent, rating + rating_opponent);
+        }
+    }
+
+    #[test]
+    fn applies_rated_game_to_profile_and_computer() {
+        let mut profiles: Vec<Profile> = vec![Profile::create("Ann")];
+        let mut ai_ratings: Vec<AiRating> = create_ai_ratings(INITIAL_RATING);
+        let winner = Participant::Profile("Ann".to_string());
+        let loser = Participant::Computer(AiLevel::Hard);
+        assert_eq!(apply_rated_game(&mut profiles, &mut ai_ratings, &winner, &loser), Ok(()));
+        assert_close(profiles[0].rating, INITIAL_RATING + K_FACTOR / 2.0);
+        assert_close(find_rating(&profiles, &ai_ratings, &loser), INITIAL_RATING - K_FACTOR / 2.0);
+        assert_close(find_rating(&profiles, &ai_ratings, &Participant::Computer(AiLevel::Easy)), INITIAL_RATING);
+    }
+
+    #[test]
+    fn reports_missing_side_and_updates_other() {
+        let mut profiles: Vec<Profile> = vec![Profile::create("Ann")];
+        let mut ai_ratings: Vec<AiRating> = Vec::new();
+        let winner = Participant::Profile("Ann".to_string());
+        let result = apply_rated_game(&mut profiles, &mut ai_ratings, &winner, &Participant::Computer(AiLevel::Easy));
+        assert!(result.is_err());
+        assert_close(profiles[0].rating, INITIAL_RATING + K_FACTOR / 2.0);
+        let result = apply_rated_game(
+            &mut profiles,
+            &mut ai_ratings,
+            &Participant::Profile("Bob".to_string()),
+            &winner,
+        );
+        assert!(result.unwrap_err().contains("Bob"));
+        assert!(profiles[0].rating < INITIAL_RATING + K_FACTOR / 2.0);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::i18n::{tr, trf};
+
+/// Kinds of ships available in the fleet configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShapeKind {
+    // Straight: The classic ship with the given number of decks in one line.
+    Straight(usize),
+    L,
+    T,
+    Square,
+    Plus,
+}
+
+impl ShapeKind {
+    /// All kinds offered in the fleet configuration.
+    pub fn all() -> Vec<ShapeKind> {
+        vec![
+            ShapeKind::Straight(1),
+            ShapeKind::Straight(2),
+            ShapeKind::Straight(3),
+            ShapeKind::Straight(4),
+            ShapeKind::L,
+            ShapeKind::T,
+            ShapeKind::Square,
+            ShapeKind::Plus,
+        ]
+    }
+
+    /// Returns the name of the kind to display to the player.
+    pub fn get_name(&self) -> String {
+        match self {
+            ShapeKind::Straight(size) => trf("shape.straight", &[size.to_string().as_str()]),
+            ShapeKind::L => tr("shape.l"),
+            ShapeKind::T => tr("shape.t"),
+            ShapeKind::Square => tr("shape.square"),
+            ShapeKind::Plus => tr("shape.plus"),
+        }
+    }
+
+    /// Returns the name of the kind that does not depend on the language, to keep in the local storage.
+    pub fn get_id(&self) -> String {
+        match self {
+            ShapeKind::Straight(size) => format!("{}-deck", size),
+            ShapeKind::L => "L-shape".to_string(),
+            ShapeKind::T => "T-shape".to_string(),
+            ShapeKind::Square => "Square".to_string(),
+            ShapeKind::Plus => "Plus".to_string(),
+        }
+    }
+
+    /// Returns the shape of the kind in its initial orientation.
+    pub fn get_shape(&self) -> Shape {
+        let cells: Vec<(usize, usize)> = match self {
+            ShapeKind::Straight(size) => (0..*size).map(|x| (x, 0)).collect(),
+            ShapeKind::L => vec![(0, 0), (0, 1), (0, 2), (1, 2)],
+            ShapeKind::T => vec![(0, 0), (1, 0), (2, 0), (1, 1)],
+            ShapeKind::Square => vec![(0, 0), (1, 0), (0, 1), (1, 1)],
+            ShapeKind::Plus => vec![(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+        };
+        Shape::from_cells(cells)
+    }
+}
+
+/// The classic fleet: one 4-deck, two 3-deck, three 2-deck and four 1-deck ships.
+pub fn get_classic_fleet() -> Vec<ShapeKind> {
+    [4, 3, 3, 2, 2, 2, 1, 1, 1, 1].iter().map(|&size| ShapeKind::Straight(size)).collect()
+}
+
+/// A fleet with ships of different shapes.
+pub fn get_shapes_fleet() -> Vec<ShapeKind> {
+    vec![
+        ShapeKind::Plus,
+        ShapeKind::L,
+        ShapeKind::T,
+        ShapeKind::Square,
+        ShapeKind::Straight(3),
+        ShapeKind::Straight(2),
+        ShapeKind::Straight(2),
+        ShapeKind::Straight(1),
+        ShapeKind::Straight(1),
+    ]
+}
+
+/// Returns a short description of the fleet, for example "4-deck x1, 3-deck x2".
+pub fn get_fleet_description(fleet: &[ShapeKind]) -> String {
+    describe_fleet(fleet, ShapeKind::get_name)
+}
+
+/// Returns the description of the fleet that does not depend on the language.
+/// The results are kept in the local storage by it.
+pub fn get_fleet_id(fleet: &[ShapeKind]) -> String {
+    describe_fleet(fleet, ShapeKind::get_id)
+}
+
+/// Returns the counts of the kinds of the fleet, each kind named by "get_name".
+fn describe_fleet(fleet: &[ShapeKind], get_name: fn(&ShapeKind) -> String) -> String {
+    ShapeKind::all().iter()
+        .filter_map(|kind| {
+            let count = fleet.iter().filter(|&k| k == kind).count();
+            if count == 0 {
+                None
+            } else {
+                Some(format!("{} x{}", get_name(kind), count))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// A set of cells relative to the upper left corner of the bounding box.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shape {
+    // cells: Sorted (x, y) offsets of the decks, the minimum x and y are 0.
+    cells: Vec<(usize, usize)>,
+}
+
+impl Shape {
+    /// Creates a shape, shifting the cells to the upper left corner.
+    pub fn from_cells(cells: Vec<(usize, usize)>) -> Self {
+        let x_min = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let y_min = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let mut cells: Vec<(usize, usize)> = cells.iter()
+            .map(|&(x, y)| (x - x_min, y - y_min))
+            .collect();
+        cells.sort_by_key(|&(x, y)| (y, x));
+        Self { cells }
+    }
+
+    pub fn get_cells(&self) -> &Vec<(usize, usize)> {
+        self.cells.as_ref()
+    }
+
+    /// Returns the number of decks.
+    pub fn get_size(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns the width of the bounding box.
+    pub fn get_width(&self) -> usize {
+        self.cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0)
+    }
+
+    /// Returns the height of the bounding box.
+    pub fn get_height(&self) -> usize {
+        self.cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0)
+    }
+
+    /// Returns the shape turned 90 degrees clockwise.
+    pub fn rotated(&self) -> Shape {
+        let height = self.get_height();
+        Shape::from_cells(self.cells.iter().map(|&(x, y)| (height - 1 - y, x)).collect())
+    }
+
+    /// Returns the mirror image of the shape.
+    pub fn reflected(&self) -> Shape {
+        let width = self.get_width();
+        Shape::from_cells(self.cells.iter().map(|&(x, y)| (width - 1 - x, y)).collect())
+    }
+
+    /// Returns all distinct rotations and reflections of the shape.
+    pub fn get_orientations(&self) -> Vec<Shape> {
+        let mut orientations: Vec<Shape> = Vec::new();
+        let mut shape = self.clone();
+        for _ in 0..4 {
+            for variant in [shape.clone(), shape.reflected()].iter() {
+                if !orientations.contains(variant) {
+                    orientations.push(variant.clone());
+                }
+            }
+            shape = shape.rotated();
+        }
+        orientations
+    }
+
+    /// Returns the indexes of the cells occupied by the shape with the given upper left corner,
+    /// or None if the shape does not fit into the field.
+    pub fn get_area(&self, idx_origin: usize, width_field: usize, height_field: usize) -> Option<Vec<usize>> {
+        let x0 = idx_origin % width_field;
+        let y0 = idx_origin / width_field;
+        if x0 + self.get_width() > width_field || y0 + self.get_height() > height_field {
+            return None;
+        }
+        Some(self.cells.iter().map(|&(x, y)| (y0 + y) * width_field + x0 + x).collect())
+    }
+}
+
+/// Returns the indexes of the cells adjacent (including diagonally) to the area,
+/// which are not part of the area.
+pub fn get_area_near(area: &[usize], width_field: usize, height_field: usize) -> Vec<usize> {
+    let mut set_idx: Vec<usize> = Vec::new();
+    for &idx in area {
+        let x = idx % width_field;
+        let y = idx / width_field;
+        let x_start = if x == 0 { 0 } else { x - 1 };
+        let y_start = if y == 0 { 0 } else { y - 1 };
+        let x_end = if x + 1 < width_field { x + 1 } else { x };
+        let y_end = if y + 1 < height_field { y + 1 } else { y };
+        for j in y_start..=y_end {
+            for i in x_start..=x_end {
+                let idx_near = j * width_field + i;
+                if !area.contains(&idx_near) && !set_idx.contains(&idx_near) {
+                    set_idx.push(idx_near);
+                }
+            }
+        }
+    }
+    set_idx
+}
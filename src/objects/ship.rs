@@ -1,18 +1,33 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::objects::shape::{Shape, ShapeKind, get_area_near};
+
+/// Possible maneuvers of an undamaged ship in the "moving fleet" variant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Maneuver {
+    Up,
+    Down,
+    Left,
+    Right,
+    // Rotate: Turn the ship 90 degrees clockwise.
+    Rotate,
+}
 
 #[derive(Debug)]
 pub struct Ship {
-    // idx_first_deck: Index of the position of the first deck of the ship.
-    idx_first_deck: usize,
-    // is_horizontal_pos: Ship position (true: horizontal, false: vertical).
-    is_horizontal_pos: bool,
+    // idx_origin: Index of the upper left corner of the bounding box of the ship.
+    idx_origin: usize,
+    // kind: The kind of the ship chosen in the fleet configuration.
+    kind: ShapeKind,
+    // shape: The current orientation of the ship.
+    shape: Shape,
     // is_alive: Ship status (false: broken; true: whole).
     is_alive: bool,
-    // size: Ship size (1,2,3 or 4)
-    size: usize,
     // fire_cell_idxes: vec with cell indices in which hit
     fire_cell_idxes: Vec<usize>,
-
+    // width_field, height_field: The size of the field on which the ship is located.
+    width_field: usize,
+    height_field: usize,
 }
 
 impl Ship {
@@ -23,125 +38,156 @@ impl Ship {
     pub fn get_fire_cell_idxes(&self) -> &Vec<usize> {
         self.fire_cell_idxes.as_ref()
     }
+    pub fn get_kind(&self) -> ShapeKind {
+        self.kind
+    }
+    /// Returns the number of decks.
+    pub fn get_size(&self) -> usize {
+        self.shape.get_size()
+    }
     /// Return is alive.
     pub fn check_is_alive(&self) -> bool { self.is_alive }
+    /// Will return "Truth" if none of the decks of the ship has been hit.
+    pub fn check_is_undamaged(&self) -> bool {
+        self.get_num_shot() == 0
+    }
     /// Will return "Truth" if the ship is alive but wounded.
     pub fn check_is_wounded(&self) -> bool {
-        self.is_alive && self.get_num_shot() < self.size && self.get_num_shot() != 0
+        self.is_alive && self.get_num_shot() < self.get_size() && self.get_num_shot() != 0
     }
     /// We carry out a successful shot at the ship. Return "true" if the ship is alive
     pub fn knock_down(&mut self, idx: usize) -> bool {
         self.fire_cell_idxes.push(idx);
         self.fire_cell_idxes.sort();
-        if self.get_num_shot() >= self.size {
+        if self.get_num_shot() >= self.get_size() {
             self.is_alive = false;
         }
         self.is_alive
     }
-    /// Setting a new location and position for the ship
-    fn _set_location_and_position(&mut self, idx_first_deck: usize, is_horizontal_pos: bool) {
-        self.idx_first_deck = idx_first_deck;
-        self.is_horizontal_pos = is_horizontal_pos;
+    /// Setting a new location and orientation for the ship
+    pub fn set_location_and_position(&mut self, idx_origin: usize, shape: Shape) {
+        self.idx_origin = idx_origin;
+        self.shape = shape;
         // Bringing the ship back to life
         self.is_alive = true;
     }
 
     /// Returns "True" if the ship has a deck with the given index
     pub fn check_idx_for_ship(&self, idx: usize) -> bool {
-        let mut result = false;
-        for &idx_ship in self.get_area_ship().iter() {
-            if idx == idx_ship {
-                result = true;
-                break;
-            }
-        };
-        result
+        self.get_area_ship().contains(&idx)
     }
 
     /// Returns the indexes of the cells that the ship occupies.
     pub fn get_area_ship(&self) -> Vec<usize> {
-        let mut set_idx: Vec<usize> = Vec::new();
-        for i in 0..self.size {
-            if self.is_horizontal_pos {
-                set_idx.push(self.idx_first_deck + i);
-            } else {
-                set_idx.push(self.idx_first_deck + i * 10);
+        self.get_area_for_position(self.idx_origin, &self.shape).unwrap_or_default()
+    }
+
+    /// Returns the indexes of the cells that the ship would occupy
+    /// with the given location and orientation, or None if it does not fit into the field.
+    pub fn get_area_for_position(&self, idx_origin: usize, shape: &Shape) -> Option<Vec<usize>> {
+        shape.get_area(idx_origin, self.width_field, self.height_field)
+    }
+
+    /// Returns the location and orientation of the ship after the maneuver,
+    /// or None if the ship would leave the playing field.
+    /// Other ships are not taken into account.
+    pub fn get_position_after_maneuver(&self, maneuver: Maneuver) -> Option<(usize, Shape)> {
+        let x = self.idx_origin % self.width_field;
+        let y = self.idx_origin / self.width_field;
+        let (x, y, shape) = match maneuver {
+            Maneuver::Up => {
+                if y == 0 { return None; }
+                (x, y - 1, self.shape.clone())
             }
+            Maneuver::Down => (x, y + 1, self.shape.clone()),
+            Maneuver::Left => {
+                if x == 0 { return None; }
+                (x - 1, y, self.shape.clone())
+            }
+            Maneuver::Right => (x + 1, y, self.shape.clone()),
+            Maneuver::Rotate => (x, y, self.shape.rotated()),
+        };
+        if y >= self.height_field {
+            return None;
         }
-        set_idx
+        let idx_origin = y * self.width_field + x;
+        self.get_area_for_position(idx_origin, &shape)?;
+        Some((idx_origin, shape))
     }
+
     /// Returns the indexes of the cells near the ship.
     pub fn get_area_near_ship(&self, area_ship: Option<Vec<usize>>) -> Vec<usize> {
-        let mut set_idx: Vec<usize> = Vec::new();
         let set_ship_idx: Vec<usize> = match area_ship {
             None => { self.get_area_ship() }
             Some(v) => { v }
         };
-        let x = self.idx_first_deck % 10;
-        let y = self.idx_first_deck / 10;
-        let y_start = if y == 0 { y } else { y - 1 };
-        let x_start = if x == 0 { x } else { x - 1 };
-        let x_end = if self.is_horizontal_pos {
-            if x + self.size <= 9 { x + self.size } else { 9 }
-        } else {
-            if x + 1 <= 9 { x + 1 } else { 9 }
-        };
-
-        let y_end = if self.is_horizontal_pos {
-            if y + 1 <= 9 { y + 1 } else { 9 }
-        } else {
-            if y + self.size <= 9 { y + self.size } else { 9 }
-        };
+        get_area_near(&set_ship_idx, self.width_field, self.height_field)
+    }
+}
 
-        let mut ship_idx_iter = set_ship_idx.iter();
-        for i in x_start..(x_end + 1) {
-            for j in y_start..(y_end + 1) {
-                match ship_idx_iter.find(|&&idx| idx == (j * 10 + i)) {
-                    None => {
-                        set_idx.push(j * 10 + i);
-                    }
-                    Some(_) => {}
-                }
-            }
+/// Returns a vector of Ships of the given kinds with a random position.
+/// The same seed always gives the same layout, without a seed the layout is random.
+/// If the fleet does not fit into the field, then the ships that could not be placed are skipped.
+pub fn get_default_fleet(
+    width_field: usize,
+    height_field: usize,
+    fleet: &[ShapeKind],
+    seed: Option<u64>,
+) -> Vec<Ship> {
+    // Large ships are placed first, while there is more free space.
+    let mut fleet: Vec<ShapeKind> = fleet.to_vec();
+    fleet.sort_by_key(|kind| std::cmp::Reverse(kind.get_shape().get_size()));
+    let mut rng: StdRng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let mut best_ships: Vec<Ship> = Vec::new();
+    for _ in 0..100 {
+        let ships = place_fleet(width_field, height_field, &fleet, &mut rng);
+        if ships.len() == fleet.len() {
+            return ships;
+        }
+        if ships.len() > best_ships.len() {
+            best_ships = ships;
         }
-        set_idx
     }
+    best_ships
 }
 
-/// Returns a vector of Ships with a random position.
-pub fn get_default_fleet() -> Vec<Ship> {
+/// Makes one attempt to place the fleet.
+/// Returns the ships that managed to be placed.
+fn place_fleet<R: Rng>(width_field: usize, height_field: usize, fleet: &[ShapeKind], rng: &mut R) -> Vec<Ship> {
     let mut ships: Vec<Ship> = Vec::new();
-    // fleet: Corresponds to the number of available ships with the value of their size.
-    let fleet: [usize; 10] = [4, 3, 3, 2, 2, 2, 1, 1, 1, 1];
     // vector_of_free_cells: Vector for tracking free cells.
     // If the element of the vector with the idx index has a value of 0,
     // then the cell of the playing field with the idx index
     // is available for placing a ship on it.
-    let mut vector_of_free_cells: Vec<usize> = vec![0; 10 * 10];
-    let mut rng = rand::thread_rng();
-    for ship_size in fleet.iter() {
-        loop {
-            let is_horizontal_pos: bool = rng.gen::<bool>();
-            // We generate indexes of the start and end of the ship, depending on the positioning.
-            let (idx_start, idx_end) = if !is_horizontal_pos {
-                let row = rng.gen_range(0, 11 - ship_size);
-                let col = rng.gen_range(0, 10);
-                (row * 10 + col, (row + ship_size - 1) * 10 + col)
-            } else {
-                let col = rng.gen_range(0, 11 - ship_size);
-                let row = rng.gen_range(0, 10);
-                (row * 10 + col, row * 10 + col + ship_size - 1)
+    let mut vector_of_free_cells: Vec<usize> = vec![0; width_field * height_field];
+    for &kind in fleet.iter() {
+        let orientations: Vec<Shape> = kind.get_shape().get_orientations();
+        for _ in 0..1000 {
+            let shape: Shape = orientations[rng.gen_range(0, orientations.len())].clone();
+            if shape.get_width() > width_field || shape.get_height() > height_field {
+                continue;
+            }
+            // We generate the index of the upper left corner so that the ship fits into the field.
+            let col = rng.gen_range(0, width_field - shape.get_width() + 1);
+            let row = rng.gen_range(0, height_field - shape.get_height() + 1);
+            let area_ship: Vec<usize> = match shape.get_area(row * width_field + col, width_field, height_field) {
+                None => { continue; }
+                Some(area) => { area }
             };
-            // We check if the generated indexes are free to place the ship.
-            if vector_of_free_cells[idx_start] == 0 && vector_of_free_cells[idx_end] == 0 {
+            // We check if the generated cells are free to place the ship.
+            if area_ship.iter().all(|&idx| vector_of_free_cells[idx] == 0) {
                 let new_ship = Ship {
-                    idx_first_deck: idx_start,
-                    is_horizontal_pos,
+                    idx_origin: row * width_field + col,
+                    kind,
+                    shape,
                     is_alive: true,
-                    size: ship_size.clone(),
                     fire_cell_idxes: Vec::new(),
+                    width_field,
+                    height_field,
                 };
-                let area_ship: Vec<usize> = new_ship.get_area_ship();
                 for &idx in &area_ship {
                     vector_of_free_cells[idx] = 1;
                 };
@@ -154,4 +200,4 @@ pub fn get_default_fleet() -> Vec<Ship> {
         }
     }
     ships
-}
\ No newline at end of file
+}
@@ -1,24 +1,70 @@
 use yew::{ComponentLink, Component, Html, html, Callback, InputData, ChangeData};
 use yew::html::Properties;
+use serde::{Deserialize, Serialize};
+use crate::objects::shape::{ShapeKind, get_classic_fleet, get_shapes_fleet};
+use crate::objects::player::AiLevel;
+use crate::objects::profile;
+use crate::storage;
+use crate::i18n::{self, tr, trf, Language};
+
+// MAX_SHIPS_OF_KIND: The maximum number of ships of the same kind in the fleet.
+const MAX_SHIPS_OF_KIND: usize = 5;
+// MIN_PLAYERS, MAX_PLAYERS: The allowed number of players in the game.
+const MIN_PLAYERS: usize = 2;
+const MAX_PLAYERS: usize = 4;
+// MIN_FIELD_SIZE, MAX_FIELD_SIZE: The allowed width and height of the fields.
+const MIN_FIELD_SIZE: usize = 5;
+const MAX_FIELD_SIZE: usize = 30;
+// TEAM_NAMES: The names of the teams that players can join.
+const TEAM_NAMES: [&str; 2] = ["settings.team_a", "settings.team_b"];
+// KEY_DISPLAY: The key of the display settings in the local storage.
+const KEY_DISPLAY: &str = "sea_battle.display";
+// RULES_CLASSIC_FLEET, RULES_SHAPES_FLEET, RULES_CUSTOM_FLEET, RULES_MOVING:
+// The parts of the description of the rules kept in the profiles.
+const RULES_CLASSIC_FLEET: &str = "classic fleet";
+const RULES_SHAPES_FLEET: &str = "shapes fleet";
+const RULES_CUSTOM_FLEET: &str = "custom fleet";
+const RULES_MOVING: &str = ", moving";
 
 pub enum MsgSettings {
     // Show setting
     SettingShowed(bool),
-    PlayerNameChange(InputData),
-    _PlayerTypeControlChange(ChangeData),
+    PlayerProfileChange(usize, ChangeData),
+    NewProfileNameChange(InputData),
+    ProfileCreated,
+    PlayerTypeControlChange(usize, ChangeData),
+    PlayerTeamChange(usize, ChangeData),
+    TwoVersusTwoChosen,
+    PlayerAdded,
+    PlayerRemoved(usize),
+    MovingFleetToggled,
+    AiLevelAutoToggled,
+    GameModeChange(ChangeData),
+    FieldWidthChange(InputData),
+    FieldHeightChange(InputData),
+    FleetCountChange(ShapeKind, InputData),
+    FleetPresetChosen(Vec<ShapeKind>),
+    ThemeChange(ChangeData),
+    LanguageChange(ChangeData),
+    GlyphsToggled,
     Submit,
 
 }
 
 #[derive(Clone, Debug, PartialEq, Properties)]
 pub struct SettingsProps {
-    pub first_player_settings: PlayerSetting,
-    pub second_player_settings: PlayerSetting,
+    pub players_settings: Vec<PlayerSetting>,
     pub field_setting: FieldSettings,
+    pub rules_setting: RulesSettings,
     pub parent_call: Callback<(
-        PlayerSetting,
-        PlayerSetting,
-        FieldSettings)>,
+        Vec<PlayerSetting>,
+        FieldSettings,
+        RulesSettings)>,
+    // display_setting, display_call: The look of the game, which is applied at once without a restart.
+    #[prop_or_default]
+    pub display_setting: DisplaySettings,
+    #[prop_or_default]
+    pub display_call: Callback<DisplaySettings>,
 }
 
 #[derive(Clone, Debug, PartialEq, Properties)]
@@ -27,6 +73,59 @@ pub struct PlayerSetting {
     pub name: String,
     // is_manual_control: If "False", then the computer controls
     pub is_manual_control: bool,
+    // ai_level: The skill of the computer, if it controls the player.
+    pub ai_level: AiLevel,
+    // team: The index of the team of the player, or None if he plays for himself.
+    pub team: Option<usize>,
+}
+
+/// Returns the name of the computer player with the given index.
+pub fn get_computer_name(idx_player: usize) -> String {
+    trf("settings.computer_name", &[idx_player.to_string().as_str()])
+}
+
+/// Returns the description of the field, fleet and rules that does not depend on the language,
+/// for example "10x10 classic fleet". Games are compared in the statistics by this description.
+pub fn get_rules_id(field_setting: &FieldSettings, rules_setting: &RulesSettings) -> String {
+    let name_fleet: &str = if field_setting.fleet == get_classic_fleet() {
+        RULES_CLASSIC_FLEET
+    } else if field_setting.fleet == get_shapes_fleet() {
+        RULES_SHAPES_FLEET
+    } else {
+        RULES_CUSTOM_FLEET
+    };
+    let name_variant: &str = if rules_setting.is_moving_fleet { RULES_MOVING } else { "" };
+    format!("{}x{} {}{}", field_setting.width_field, field_setting.height_field, name_fleet, name_variant)
+}
+
+/// Returns the description of the rules made by "get_rules_id" in the current language.
+/// A description in an unknown form is returned as it is.
+pub fn describe_rules(rules_id: &str) -> String {
+    let (rules_fleet, is_moving_fleet): (&str, bool) = match rules_id.strip_suffix(RULES_MOVING) {
+        None => { (rules_id, false) }
+        Some(rules_fleet) => { (rules_fleet, true) }
+    };
+    let (size_field, name_fleet): (&str, &str) = match rules_fleet.split_once(' ') {
+        None => { return rules_id.to_string(); }
+        Some(parts) => { parts }
+    };
+    let key_fleet: &str = match name_fleet {
+        RULES_CLASSIC_FLEET => { "settings.rules_classic" }
+        RULES_SHAPES_FLEET => { "settings.rules_shapes" }
+        RULES_CUSTOM_FLEET => { "settings.rules_custom" }
+        _ => { return rules_id.to_string(); }
+    };
+    let description: String = trf(key_fleet, &[size_field]);
+    if is_moving_fleet {
+        trf("settings.rules_moving", &[description.as_str()])
+    } else {
+        description
+    }
+}
+
+/// Returns the name of the team with the given index.
+pub fn get_team_name(team: usize) -> String {
+    TEAM_NAMES.get(team).map_or(trf("settings.team_number", &[(team + 1).to_string().as_str()]), |key| tr(key))
 }
 
 #[derive(Clone, Debug, PartialEq, Properties)]
@@ -34,15 +133,112 @@ pub struct PlayerSetting {
 pub struct FieldSettings {
     pub width_field: usize,
     pub height_field: usize,
+    // fleet: Kinds of ships placed on each field.
+    pub fleet: Vec<ShapeKind>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Available kinds of games
+pub enum GameMode {
+    // Battle: The players shoot at each other's fleets.
+    Battle,
+    // Solo: One player sinks a generated fleet without enemy fire, for shots and time.
+    Solo,
+    // Daily: A solo game with the same fleet for everyone on the same date.
+    Daily,
+    // Puzzle: Battleship solitaire, the fleet is found from the numbers of decks in rows and columns.
+    Puzzle,
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+/// Options available to customize the rules of the game
+pub struct RulesSettings {
+    // is_moving_fleet: If "True", then instead of a shot,
+    // the player can move or rotate one of his undamaged ships.
+    pub is_moving_fleet: bool,
+    pub mode: GameMode,
+    // is_ai_level_auto: If "True", then the computer players get the level
+    // whose rating is closest to the rating of the human player.
+    pub is_ai_level_auto: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+/// Available colour schemes of the game
+pub enum Theme {
+    Light,
+    Dark,
+    HighContrast,
+    // ColorBlind: The palette that stays distinguishable with any kind of colour blindness.
+    ColorBlind,
+}
+
+impl Theme {
+    pub fn all() -> Vec<Theme> {
+        vec![Theme::Light, Theme::Dark, Theme::HighContrast, Theme::ColorBlind]
+    }
+
+    /// Returns the identifier of the theme used in the page styles and in the form.
+    pub fn get_id(&self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::HighContrast => "high-contrast",
+            Theme::ColorBlind => "color-blind",
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            Theme::Light => tr("settings.theme_light"),
+            Theme::Dark => tr("settings.theme_dark"),
+            Theme::HighContrast => tr("settings.theme_high_contrast"),
+            Theme::ColorBlind => tr("settings.theme_color_blind"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Options of the look of the game, kept between visits
+pub struct DisplaySettings {
+    pub theme: Theme,
+    // is_glyphs_shown: If "True", then the states of the cells are also marked by symbols,
+    // a dot for a miss, a cross for a hit and an outline for a sunk ship.
+    pub is_glyphs_shown: bool,
+    #[serde(default)]
+    pub language: Language,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::Light,
+            is_glyphs_shown: true,
+            language: Language::English,
+        }
+    }
+}
+
+/// Restores the display settings from the local storage.
+pub fn load_display_settings() -> DisplaySettings {
+    storage::load(KEY_DISPLAY)
+}
+
+/// Saves the display settings to the local storage.
+pub fn save_display_settings(display_setting: &DisplaySettings) {
+    storage::save(KEY_DISPLAY, display_setting);
 }
 
 pub struct SettingsForm {
     link: ComponentLink<Self>,
     props: SettingsProps,
     visible: bool,
-    first_player_settings: PlayerSetting,
-    second_player_settings: PlayerSetting,
+    players_settings: Vec<PlayerSetting>,
     field_setting: FieldSettings,
+    rules_setting: RulesSettings,
+    // profile_names: The names of the saved profiles that human players can choose.
+    profile_names: Vec<String>,
+    // new_profile_name: The name entered for a new profile.
+    new_profile_name: String,
 }
 
 impl Component for SettingsForm {
@@ -50,16 +246,18 @@ impl Component for SettingsForm {
     type Properties = SettingsProps;
 
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
-        let first_player_settings = props.first_player_settings.clone();
-        let second_player_settings = props.second_player_settings.clone();
+        let players_settings = props.players_settings.clone();
         let field_setting = props.field_setting.clone();
+        let rules_setting = props.rules_setting.clone();
         Self {
             link,
             props,
             visible: false,
-            first_player_settings,
-            second_player_settings,
+            players_settings,
             field_setting,
+            rules_setting,
+            profile_names: load_profile_names(),
+            new_profile_name: String::new(),
         }
     }
 
@@ -67,31 +265,188 @@ impl Component for SettingsForm {
         match msg {
             MsgSettings::SettingShowed(is_show) => {
                 self.visible = is_show;
+                // The profiles could have been imported while the settings were hidden.
+                self.profile_names = load_profile_names();
                 true
             }
-            MsgSettings::PlayerNameChange(e) => {
-                self.first_player_settings.name = e.value;
+            MsgSettings::PlayerProfileChange(idx_player, e) => {
+                if let ChangeData::Select(el) = e {
+                    if let Some(player_settings) = self.players_settings.get_mut(idx_player) {
+                        player_settings.name = el.value();
+                    }
+                }
                 true
             }
-            MsgSettings::_PlayerTypeControlChange(e) => {
+            MsgSettings::NewProfileNameChange(e) => {
+                self.new_profile_name = e.value;
+                false
+            }
+            MsgSettings::ProfileCreated => {
+                let name: String = self.new_profile_name.trim().to_string();
+                if name.is_empty() || self.profile_names.contains(&name) {
+                    return false;
+                }
+                let mut profiles = profile::load_profiles();
+                profiles.push(profile::Profile::create(name.as_str()));
+                profile::save_profiles(&profiles);
+                self.profile_names = load_profile_names();
+                self.new_profile_name.clear();
+                true
+            }
+            MsgSettings::PlayerTypeControlChange(idx_player, e) => {
                 match e {
                     ChangeData::Value(_) => {}
                     ChangeData::Select(el) => {
-                        if el.value() == "1" {
-                            self.first_player_settings.is_manual_control = true;
-                        } else {
-                            self.first_player_settings.is_manual_control = false;
+                        let name_profile: String = self.profile_names.first()
+                            .cloned()
+                            .unwrap_or_else(|| profile::DEFAULT_PROFILE_NAME.to_string());
+                        if let Some(player_settings) = self.players_settings.get_mut(idx_player) {
+                            let is_manual_control: bool = el.value() == "1";
+                            if is_manual_control != player_settings.is_manual_control {
+                                player_settings.name = if is_manual_control {
+                                    name_profile
+                                } else {
+                                    get_computer_name(idx_player)
+                                };
+                            }
+                            player_settings.is_manual_control = is_manual_control;
+                            player_settings.ai_level = match el.value().as_str() {
+                                "easy" => AiLevel::Easy,
+                                "normal" => AiLevel::Normal,
+                                _ => AiLevel::Hard,
+                            };
                         }
                     }
                     ChangeData::Files(_) => {}
                 };
                 true
             }
+            MsgSettings::PlayerTeamChange(idx_player, e) => {
+                if let ChangeData::Select(el) = e {
+                    if let Some(player_settings) = self.players_settings.get_mut(idx_player) {
+                        player_settings.team = el.value().parse::<usize>().ok();
+                    }
+                }
+                true
+            }
+            MsgSettings::TwoVersusTwoChosen => {
+                let name_human = self.players_settings.iter()
+                    .find(|player_settings| player_settings.is_manual_control)
+                    .map_or(profile::DEFAULT_PROFILE_NAME.to_string(), |player_settings| player_settings.name.clone());
+                let ai_level: AiLevel = self.players_settings.iter()
+                    .find(|player_settings| !player_settings.is_manual_control)
+                    .map_or(AiLevel::Hard, |player_settings| player_settings.ai_level);
+                self.players_settings = vec![
+                    PlayerSetting { name: name_human, is_manual_control: true, ai_level, team: Some(0) },
+                    PlayerSetting { name: get_computer_name(1), is_manual_control: false, ai_level, team: Some(1) },
+                    PlayerSetting { name: get_computer_name(2), is_manual_control: false, ai_level, team: Some(0) },
+                    PlayerSetting { name: get_computer_name(3), is_manual_control: false, ai_level, team: Some(1) },
+                ];
+                true
+            }
+            MsgSettings::PlayerAdded => {
+                if self.players_settings.len() >= MAX_PLAYERS {
+                    return false;
+                }
+                self.players_settings.push(PlayerSetting {
+                    name: get_computer_name(self.players_settings.len()),
+                    is_manual_control: false,
+                    ai_level: AiLevel::Hard,
+                    team: None,
+                });
+                true
+            }
+            MsgSettings::PlayerRemoved(idx_player) => {
+                if self.players_settings.len() <= MIN_PLAYERS {
+                    return false;
+                }
+                self.players_settings.remove(idx_player);
+                true
+            }
+            MsgSettings::MovingFleetToggled => {
+                self.rules_setting.is_moving_fleet = !self.rules_setting.is_moving_fleet;
+                true
+            }
+            MsgSettings::AiLevelAutoToggled => {
+                self.rules_setting.is_ai_level_auto = !self.rules_setting.is_ai_level_auto;
+                true
+            }
+            MsgSettings::GameModeChange(e) => {
+                if let ChangeData::Select(el) = e {
+                    self.rules_setting.mode = match el.value().as_str() {
+                        "solo" => GameMode::Solo,
+                        "daily" => GameMode::Daily,
+                        "puzzle" => GameMode::Puzzle,
+                        _ => GameMode::Battle,
+                    };
+                }
+                true
+            }
+            MsgSettings::FieldWidthChange(e) => {
+                if let Ok(width_field) = e.value.parse::<usize>() {
+                    self.field_setting.width_field = width_field.clamp(MIN_FIELD_SIZE, MAX_FIELD_SIZE);
+                }
+                true
+            }
+            MsgSettings::FieldHeightChange(e) => {
+                if let Ok(height_field) = e.value.parse::<usize>() {
+                    self.field_setting.height_field = height_field.clamp(MIN_FIELD_SIZE, MAX_FIELD_SIZE);
+                }
+                true
+            }
+            MsgSettings::FleetCountChange(kind, e) => {
+                let count: usize = e.value.parse::<usize>().unwrap_or(0).min(MAX_SHIPS_OF_KIND);
+                let fleet: &Vec<ShapeKind> = &self.field_setting.fleet;
+                self.field_setting.fleet = ShapeKind::all().iter()
+                    .flat_map(|&other| {
+                        let count_other = if other == kind {
+                            count
+                        } else {
+                            fleet.iter().filter(|&&k| k == other).count()
+                        };
+                        vec![other; count_other]
+                    })
+                    .collect();
+                true
+            }
+            MsgSettings::FleetPresetChosen(fleet) => {
+                self.field_setting.fleet = fleet;
+                true
+            }
+            MsgSettings::ThemeChange(e) => {
+                if let ChangeData::Select(el) = e {
+                    let mut display_setting: DisplaySettings = self.props.display_setting.clone();
+                    display_setting.theme = Theme::all().into_iter()
+                        .find(|theme| theme.get_id() == el.value())
+                        .unwrap_or(Theme::Light);
+                    self.props.display_call.emit(display_setting);
+                }
+                false
+            }
+            MsgSettings::LanguageChange(e) => {
+                if let ChangeData::Select(el) = e {
+                    let mut display_setting: DisplaySettings = self.props.display_setting.clone();
+                    display_setting.language = Language::all().into_iter()
+                        .find(|language| language.get_id() == el.value())
+                        .unwrap_or_default();
+                    self.props.display_call.emit(display_setting);
+                }
+                false
+            }
+            MsgSettings::GlyphsToggled => {
+                let mut display_setting: DisplaySettings = self.props.display_setting.clone();
+                display_setting.is_glyphs_shown = !display_setting.is_glyphs_shown;
+                self.props.display_call.emit(display_setting);
+                false
+            }
             MsgSettings::Submit => {
+                if self.field_setting.fleet.is_empty() || !self.has_opponents() {
+                    return false;
+                }
                 self.props.parent_call.emit(
-                    (self.first_player_settings.clone(),
-                    self.second_player_settings.clone(),
-                    self.field_setting.clone()));
+                    (self.players_settings.clone(),
+                    self.field_setting.clone(),
+                    self.rules_setting.clone()));
                 self.link.callback(MsgSettings::SettingShowed).emit(false);
                 true
             }
@@ -106,56 +461,168 @@ impl Component for SettingsForm {
     fn view(&self) -> Html {
         let is_need_to_show: bool = !self.visible;
         let text_button: String = if self.visible {
-            "Hide Settings".to_string()
+            tr("settings.hide")
         } else {
-            "Show Settings".to_string()
+            tr("settings.show")
         };
 
-        // let select_type_control = if self.first_player_settings.is_manual_control {
-        //     html! {
-        //         <select class="element select medium" id="element_2" name="element_2"
-        //         onchange=self.link.callback(|e| MsgSettings::PlayerTypeControlChange(e))>
-        //         <option value="1" selected=true >{ "Manual control" }</option>
-        //         <option value="2" >{ "Computer control" }</option>
-        //         </select>
-        //     }
-        // } else {
-        //     html! {
-        //         <select class="element select medium" id="element_2" name="element_2"
-        //         onchange=self.link.callback(|e| MsgSettings::PlayerTypeControlChange(e))>
-        //         <option value="1" >{ "Manual control" }</option>
-        //         <option value="2" selected=true >{ "Computer control" }</option>
-        //         </select>
-        //     }
-        // };
-
         let form_settings = if self.visible {
             html! {
                 <>
                 <ul>
-                    <li id="li_1" >
-                        <label class="description" for="element_1">{ "Name Player" }</label>
+                    { for self.players_settings.iter().enumerate()
+                        .map(|(idx_player, player_settings)| self.view_player(idx_player, player_settings)) }
+                    <li>
+                        <button
+                            disabled={ self.players_settings.len() >= MAX_PLAYERS }
+                            onclick=self.link.callback(|_| MsgSettings::PlayerAdded)>
+                            { tr("settings.add_player") }
+                        </button>
+                        <button onclick=self.link.callback(|_| MsgSettings::TwoVersusTwoChosen)>
+                            { tr("settings.two_vs_two") }
+                        </button>
+                    </li>
+                    <li>
+                        <label class="description" for="element_8">{ tr("settings.new_profile") }</label>
                         <div>
                             <input
-                                id="element_1"
-                                name="element_1"
+                                id="element_8"
                                 class="element text medium"
                                 type="text"
-                                maxlength="255"
-                                value=self.first_player_settings.name.to_owned()
-                                oninput=self.link.callback(|e| MsgSettings::PlayerNameChange(e))
+                                maxlength="64"
+                                value=self.new_profile_name.clone()
+                                oninput=self.link.callback(MsgSettings::NewProfileNameChange)
+                            />
+                            <button onclick=self.link.callback(|_| MsgSettings::ProfileCreated)>
+                                { tr("settings.create") }
+                            </button>
+                        </div>
+                    </li>
+                    <li id="li_6" >
+                        <label class="description" for="element_6">{ tr("settings.game_mode") }</label>
+                        <div>
+                            <select class="element select medium" id="element_6" name="element_6"
+                                onchange=self.link.callback(MsgSettings::GameModeChange)>
+                                <option value="battle" selected={ self.rules_setting.mode == GameMode::Battle }>
+                                    { tr("settings.mode_battle") }
+                                </option>
+                                <option value="solo" selected={ self.rules_setting.mode == GameMode::Solo }>
+                                    { tr("settings.mode_solo") }
+                                </option>
+                                <option value="daily" selected={ self.rules_setting.mode == GameMode::Daily }>
+                                    { tr("settings.mode_daily") }
+                                </option>
+                                <option value="puzzle" selected={ self.rules_setting.mode == GameMode::Puzzle }>
+                                    { tr("settings.mode_puzzle") }
+                                </option>
+                            </select>
+                        </div>
+                    </li>
+                    <li id="li_7" >
+                        <label class="description" for="element_7">{ tr("settings.field_size") }</label>
+                        <div>
+                            <input
+                                id="element_7"
+                                class="element text small"
+                                type="number"
+                                min=MIN_FIELD_SIZE.to_string()
+                                max=MAX_FIELD_SIZE.to_string()
+                                value=self.field_setting.width_field.to_string()
+                                oninput=self.link.callback(MsgSettings::FieldWidthChange)
+                            />
+                            { " x " }
+                            <input
+                                class="element text small"
+                                type="number"
+                                min=MIN_FIELD_SIZE.to_string()
+                                max=MAX_FIELD_SIZE.to_string()
+                                value=self.field_setting.height_field.to_string()
+                                oninput=self.link.callback(MsgSettings::FieldHeightChange)
+                            />
+                        </div>
+                    </li>
+                    <li id="li_3" >
+                        <label class="description" for="element_3">{ tr("settings.moving_fleet") }</label>
+                        <div>
+                            <input
+                                id="element_3"
+                                name="element_3"
+                                class="element checkbox"
+                                type="checkbox"
+                                checked=self.rules_setting.is_moving_fleet
+                                onclick=self.link.callback(|_| MsgSettings::MovingFleetToggled)
                             />
                         </div>
                     </li>
-                    // <li id="li_2" >
-                    //     <label class="description" for="element_2">{ "Control type" }</label>
-                    //     <div>
-                    //         { select_type_control }
-                    //     </div>
-                    // </li>
+                    <li id="li_9" >
+                        <label class="description" for="element_9">{ tr("settings.ai_level_auto") }</label>
+                        <div>
+                            <input
+                                id="element_9"
+                                name="element_9"
+                                class="element checkbox"
+                                type="checkbox"
+                                checked=self.rules_setting.is_ai_level_auto
+                                onclick=self.link.callback(|_| MsgSettings::AiLevelAutoToggled)
+                            />
+                        </div>
+                    </li>
+                    <li id="li_4" >
+                        <label class="description">{ tr("settings.fleet") }</label>
+                        <div>
+                            <button onclick=self.link.callback(|_|
+                                MsgSettings::FleetPresetChosen(get_classic_fleet()))>
+                                { tr("settings.fleet_classic") }
+                            </button>
+                            <button onclick=self.link.callback(|_|
+                                MsgSettings::FleetPresetChosen(get_shapes_fleet()))>
+                                { tr("settings.fleet_shapes") }
+                            </button>
+                        </div>
+                        { for ShapeKind::all().into_iter().map(|kind| self.view_fleet_count(kind)) }
+                    </li>
+                    <li id="li_10" >
+                        <label class="description" for="element_10">{ tr("settings.theme") }</label>
+                        <div>
+                            <select class="element select medium" id="element_10" name="element_10"
+                                onchange=self.link.callback(MsgSettings::ThemeChange)>
+                                { for Theme::all().into_iter().map(|theme| html! {
+                                    <option value=theme.get_id() selected={ theme == self.props.display_setting.theme }>
+                                        { theme.get_name() }
+                                    </option>
+                                }) }
+                            </select>
+                        </div>
+                    </li>
+                    <li id="li_11" >
+                        <label class="description" for="element_11">{ tr("settings.glyphs") }</label>
+                        <div>
+                            <input
+                                id="element_11"
+                                name="element_11"
+                                class="element checkbox"
+                                type="checkbox"
+                                checked=self.props.display_setting.is_glyphs_shown
+                                onclick=self.link.callback(|_| MsgSettings::GlyphsToggled)
+                            />
+                        </div>
+                    </li>
+                    <li id="li_12" >
+                        <label class="description" for="element_12">{ tr("settings.language") }</label>
+                        <div>
+                            <select class="element select medium" id="element_12" name="element_12"
+                                onchange=self.link.callback(MsgSettings::LanguageChange)>
+                                { for Language::all().into_iter().map(|language| html! {
+                                    <option value=language.get_id() selected={ language == i18n::get_language() }>
+                                        { language.get_name() }
+                                    </option>
+                                }) }
+                            </select>
+                        </div>
+                    </li>
                     <li class="buttons">
                         <button onclick=self.link.callback(|_| MsgSettings::Submit)>
-                            { "Submit" }
+                            { tr("settings.submit") }
                         </button>
                     </li>
                 </ul>
@@ -179,4 +646,116 @@ impl Component for SettingsForm {
             </>
         }
     }
-}
\ No newline at end of file
+}
+
+impl SettingsForm {
+    /// Returns "True" if at least two players are not in the same team.
+    fn has_opponents(&self) -> bool {
+        self.players_settings.iter().enumerate().any(|(idx_player, player_settings)| {
+            self.players_settings.iter().enumerate().any(|(idx_other, other_settings)| {
+                idx_player != idx_other
+                    && (player_settings.team.is_none() || player_settings.team != other_settings.team)
+            })
+        })
+    }
+
+    /// Renders the profile or name and the control type of the player.
+    fn view_player(&self, idx_player: usize, player_settings: &PlayerSetting) -> Html {
+        let id_name = format!("element_1_{}", idx_player);
+        let id_control = format!("element_2_{}", idx_player);
+        let id_team = format!("element_5_{}", idx_player);
+        // The human players choose one of the profiles, the computer players have fixed names.
+        let input_name: Html = if player_settings.is_manual_control {
+            let mut profile_names: Vec<String> = self.profile_names.clone();
+            if !profile_names.contains(&player_settings.name) {
+                profile_names.insert(0, player_settings.name.clone());
+            }
+            html! {
+                <select class="element select medium" id=id_name.clone() name=id_name.clone()
+                    onchange=self.link.callback(move |e| MsgSettings::PlayerProfileChange(idx_player, e))>
+                    { for profile_names.iter().map(|name| html! {
+                        <option value=name.clone() selected={ *name == player_settings.name }>
+                            { name.clone() }
+                        </option>
+                    }) }
+                </select>
+            }
+        } else {
+            html! { <span id=id_name.clone()>{ player_settings.name.clone() }</span> }
+        };
+        let is_level = |ai_level: AiLevel| !player_settings.is_manual_control && player_settings.ai_level == ai_level;
+        html! {
+            <li class="player-settings">
+                <label class="description" for=id_name.clone()>
+                    { trf("settings.player", &[(idx_player + 1).to_string().as_str()]) }
+                </label>
+                <div>
+                    { input_name }
+                </div>
+                <label class="description" for=id_control.clone()>{ tr("settings.control") }</label>
+                <div>
+                    <select class="element select medium" id=id_control.clone() name=id_control
+                        onchange=self.link.callback(move |e| MsgSettings::PlayerTypeControlChange(idx_player, e))>
+                        <option value="1" selected=player_settings.is_manual_control>
+                            { tr("settings.manual") }
+                        </option>
+                        <option value="easy" selected=is_level(AiLevel::Easy)>
+                            { tr("settings.computer_easy") }
+                        </option>
+                        <option value="normal" selected=is_level(AiLevel::Normal)>
+                            { tr("settings.computer_normal") }
+                        </option>
+                        <option value="hard" selected=is_level(AiLevel::Hard)>
+                            { tr("settings.computer_hard") }
+                        </option>
+                    </select>
+                </div>
+                <label class="description" for=id_team.clone()>{ tr("settings.team") }</label>
+                <div>
+                    <select class="element select medium" id=id_team.clone() name=id_team
+                        onchange=self.link.callback(move |e| MsgSettings::PlayerTeamChange(idx_player, e))>
+                        <option value="" selected=player_settings.team.is_none()>{ tr("settings.no_team") }</option>
+                        { for (0..TEAM_NAMES.len()).map(|team| html! {
+                            <option value=team.to_string() selected={ player_settings.team == Some(team) }>
+                                { get_team_name(team) }
+                            </option>
+                        }) }
+                    </select>
+                    <button
+                        disabled={ self.players_settings.len() <= MIN_PLAYERS }
+                        onclick=self.link.callback(move |_| MsgSettings::PlayerRemoved(idx_player))>
+                        { tr("settings.remove") }
+                    </button>
+                </div>
+            </li>
+        }
+    }
+
+    /// Renders the input of the number of ships of the given kind in the fleet.
+    fn view_fleet_count(&self, kind: ShapeKind) -> Html {
+        let count: usize = self.field_setting.fleet.iter().filter(|&&k| k == kind).count();
+        html! {
+            <div class="fleet-count">
+                <label>{ kind.get_name() }</label>
+                <input
+                    class="element text small"
+                    type="number"
+                    min="0"
+                    max=MAX_SHIPS_OF_KIND.to_string()
+                    value=count.to_string()
+                    oninput=self.link.callback(move |e| MsgSettings::FleetCountChange(kind, e))
+                />
+            </div>
+        }
+    }
+}
+
+/// Returns the names of the saved profiles, or the default profile if there are none.
+fn load_profile_names() -> Vec<String> {
+    let names: Vec<String> = profile::load_profiles().into_iter().map(|profile| profile.name).collect();
+    if names.is_empty() {
+        vec![profile::DEFAULT_PROFILE_NAME.to_string()]
+    } else {
+        names
+    }
+}
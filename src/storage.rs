@@ -0,0 +1,35 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use yew::format::{Json, Text};
+use yew::services::storage::{Area, StorageService};
+
+/// Restores the value saved under the key in the local storage of the browser.
+/// Returns the default value if nothing was saved or the data cannot be read.
+pub fn load<T: DeserializeOwned + Default>(key: &str) -> T {
+    match StorageService::new(Area::Local) {
+        Err(_) => { T::default() }
+        Ok(storage) => {
+            let Json(data): Json<Result<T, _>> = storage.restore(key);
+            data.unwrap_or_default()
+        }
+    }
+}
+
+/// Saves the value under the key in the local storage of the browser.
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    if let Ok(mut storage) = StorageService::new(Area::Local) {
+        storage.store(key, Json(value));
+    }
+}
+
+/// Returns the value as JSON text, or None if it cannot be converted.
+pub fn to_json<T: Serialize>(value: &T) -> Option<String> {
+    let text: Text = Json(value).into();
+    text.ok()
+}
+
+/// Reads the value from JSON text, or returns None if the text is not valid.
+pub fn from_json<T: DeserializeOwned>(text: &str) -> Option<T> {
+    let Json(data): Json<Result<T, _>> = Json::from(Ok(text.to_string()) as Text);
+    data.ok()
+}